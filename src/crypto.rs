@@ -10,8 +10,153 @@ use cbc::{Decryptor, Encryptor};
 use cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
 use rand::rngs::OsRng;
 use rand::TryRngCore;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Current version of the [`EncryptedPayload`] format (AES-256-CBC)
+pub const PAYLOAD_VERSION: u8 = 1;
+
+/// Parsed encrypted data
+///
+/// Holds the version, IV, ciphertext and tag of an encrypted value, so malformed data
+/// can be rejected without the secret key.
+///
+/// The string format for version 1 is `<iv hex>:<ciphertext hex>`, which is what
+/// [`Crypto::encrypt`] returns. An optional `v<version>:` prefix is accepted.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::EncryptedPayload;
+///
+/// let payload: EncryptedPayload = "000102030405060708090a0b0c0d0e0f:000102030405060708090a0b0c0d0e0f"
+///     .parse()
+///     .expect("Invalid payload");
+/// assert_eq!(payload.version(), 1);
+///
+/// assert!("not encrypted".parse::<EncryptedPayload>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedPayload {
+    version: u8,
+    iv: Vec<u8>,
+    ciphertext: Vec<u8>,
+    tag: Option<Vec<u8>>,
+}
+
+impl EncryptedPayload {
+    /// Format version of the payload
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Initialization vector (nonce)
+    pub fn iv(&self) -> &[u8] {
+        &self.iv
+    }
+
+    /// Encrypted data
+    pub fn ciphertext(&self) -> &[u8] {
+        &self.ciphertext
+    }
+
+    /// Authentication tag. Always `None` for version 1, which is not authenticated
+    pub fn tag(&self) -> Option<&[u8]> {
+        self.tag.as_deref()
+    }
+}
+
+impl FromStr for EncryptedPayload {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("data is empty".into());
+        }
+
+        let mut parts: Vec<&str> = s.split(':').collect();
+
+        // Prefix versi opsional, contoh: "v1:<iv>:<ciphertext>"
+        let mut version = PAYLOAD_VERSION;
+        if let Some(prefix) = parts[0].strip_prefix('v') {
+            version = match prefix.parse::<u8>() {
+                Ok(v) => v,
+                Err(_) => return Err(format!("Invalid payload version: {}", prefix))?,
+            };
+            parts.remove(0);
+        }
+        if version != PAYLOAD_VERSION {
+            return Err(format!("Unsupported payload version: {}", version))?;
+        }
+
+        if parts.len() != 2 {
+            return Err("Invalid encrypted format. Expected ':' separator.".into());
+        }
+
+        let iv = match hex::decode(parts[0]) {
+            Ok(data) => data,
+            Err(e) => return Err(format!("Failed decode hex: {}", e))?,
+        };
+        if iv.len() != 16 {
+            return Err("Invalid IV length.".into());
+        }
+
+        let ciphertext = match hex::decode(parts[1]) {
+            Ok(data) => data,
+            Err(e) => return Err(format!("Failed decode hex: {}", e))?,
+        };
+        // Pastikan ciphertext panjangnya kelipatan 16 (ukuran blok AES)
+        if ciphertext.is_empty() || ciphertext.len() % 16 != 0 {
+            return Err("Ciphertext is not a multiple of the block size.".into());
+        }
+
+        Ok(EncryptedPayload {
+            version,
+            iv,
+            ciphertext,
+            tag: None,
+        })
+    }
+}
+
+impl fmt::Display for EncryptedPayload {
+    /// Writes `<iv hex>:<ciphertext hex>`, the format returned by [`Crypto::encrypt`]
+    ///
+    /// Version 1 is written without the `v1:` prefix so existing data stays compatible. The tag
+    /// is not written, because no supported version has one yet.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.version != PAYLOAD_VERSION {
+            write!(f, "v{}:", self.version)?;
+        }
+        write!(
+            f,
+            "{}:{}",
+            hex::encode(&self.iv),
+            hex::encode(&self.ciphertext)
+        )
+    }
+}
+
+impl Serialize for EncryptedPayload {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EncryptedPayload {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
 
 /// Crypto instance
 pub struct Crypto {
@@ -41,6 +186,12 @@ impl Crypto {
     /// println!("{}",encrypted_data);
     /// ```
     pub fn encrypt(&self, data: String) -> Result<String, Box<dyn Error>> {
+        let payload = self.encrypt_payload(data)?;
+        Ok(payload.to_string())
+    }
+
+    /// Encrypt string data into [`EncryptedPayload`]
+    pub fn encrypt_payload(&self, data: String) -> Result<EncryptedPayload, Box<dyn Error>> {
         let plain_text = data.as_bytes();
 
         // if self.key.len() != 16 {
@@ -70,15 +221,12 @@ impl Crypto {
             encryptor.encrypt_block_mut(chunk.into());
         }
 
-        // Gabungkan IV dengan ciphertext
-        let mut result = Vec::new();
-        result.extend_from_slice(&iv);
-        result.extend_from_slice(&buffer);
-
-        // Encode hasil ke dalam format hex
-        let encrypted_text = hex::encode(result);
-        let formatted = format!("{}:{}", &encrypted_text[..32], &encrypted_text[32..]);
-        Ok(formatted)
+        Ok(EncryptedPayload {
+            version: PAYLOAD_VERSION,
+            iv: iv.to_vec(),
+            ciphertext: buffer,
+            tag: None,
+        })
     }
 
     /// Encrypt struct data
@@ -90,10 +238,7 @@ impl Crypto {
     {
         use serde_json::to_string;
 
-        let data_string = match to_string(data) {
-            Ok(dt) => dt,
-            Err(e) => return Err(e.into()),
-        };
+        let data_string = to_string(data)?;
 
         self.encrypt(data_string)
     }
@@ -109,35 +254,14 @@ impl Crypto {
     /// println!("{}",decrypted_data.unwrap_or("failed".to_string()));
     /// ```
     pub fn decrypt(&self, encrypted: String) -> Result<String, Box<dyn Error>> {
-        if encrypted.is_empty() {
-            return Err("data is empty".into());
-        }
-
-        // Split encrypted data menjadi IV dan ciphertext
-        let parts: Vec<&str> = encrypted.split(':').collect();
-        if parts.len() != 2 {
-            return Err("Invalid encrypted format. Expected ':' separator.".into());
-        }
-
-        // Gabungkan kembali bagian encrypted
-        let encrypted_data = format!("{}{}", parts[0], parts[1]);
-        let cipher_text = match hex::decode(encrypted_data) {
-            Ok(data) => data,
-            Err(e) => return Err(format!("Failed decode hex: {}", e))?,
-        };
-
-        if cipher_text.len() < 16 {
-            return Err("Ciphertext too short.".into());
-        }
-
-        // Pisahkan IV dan ciphertext
-        let iv = &cipher_text[..16];
-        let cipher_text = &cipher_text[16..];
+        let payload: EncryptedPayload = encrypted.parse()?;
+        self.decrypt_payload(&payload)
+    }
 
-        // Pastikan ciphertext panjangnya kelipatan 16 (ukuran blok AES)
-        if cipher_text.len() % 16 != 0 {
-            return Err("Ciphertext is not a multiple of the block size.".into());
-        }
+    /// Decrypt [`EncryptedPayload`] into string data
+    pub fn decrypt_payload(&self, payload: &EncryptedPayload) -> Result<String, Box<dyn Error>> {
+        let iv = payload.iv();
+        let cipher_text = payload.ciphertext();
 
         // Inisialisasi decryptor
         let mut decryptor = match Decryptor::<Aes256>::new_from_slices(&self.key, iv) {
@@ -176,10 +300,7 @@ impl Crypto {
 
         let decrypted = self.decrypt(data)?;
 
-        let data: T = match from_str(&decrypted) {
-            Ok(data) => data,
-            Err(err) => return Err(err.into()),
-        };
+        let data: T = from_str(&decrypted)?;
 
         Ok(data)
     }
@@ -205,6 +326,24 @@ fn unpad_pkcs7(data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
     Err("Invalid padding".into())
 }
 
+/// Compare two byte slices in constant time
+///
+/// Does not return early at the first different byte, so the time taken does not reveal how much
+/// of a secret matches. Slices with different lengths are never equal.
+///
+/// ## Example
+///
+/// ```
+/// assert!(pn_utils::constant_time_eq(b"secret token", b"secret token"));
+/// assert!(!pn_utils::constant_time_eq(b"secret token", b"secret tokem"));
+/// ```
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -263,4 +402,82 @@ mod tests {
         assert_eq!(origin_data.name, _decrypted.name);
         assert_eq!(origin_data.age, _decrypted.age);
     }
+
+    #[test]
+    fn payload_roundtrip() {
+        let crypto = Crypto::new("c67106b30d41345119309c05d1c4ab28".to_string());
+
+        let encrypted = crypto
+            .encrypt(String::from("halo ini data"))
+            .expect("Failed to encrypt");
+        let payload: EncryptedPayload = encrypted.parse().expect("Failed to parse payload");
+
+        assert_eq!(payload.version(), PAYLOAD_VERSION);
+        assert_eq!(payload.iv().len(), 16);
+        assert_eq!(payload.tag(), None);
+        assert_eq!(payload.to_string(), encrypted);
+
+        let prefixed: EncryptedPayload = format!("v1:{}", encrypted)
+            .parse()
+            .expect("Failed to parse prefixed payload");
+        assert_eq!(prefixed, payload);
+        let reparsed: EncryptedPayload = prefixed
+            .to_string()
+            .parse()
+            .expect("Failed to parse displayed payload");
+        assert_eq!(reparsed, prefixed);
+
+        let decrypted = crypto.decrypt_payload(&payload).expect("Failed to decrypt");
+        assert_eq!(decrypted, "halo ini data");
+    }
+
+    #[test]
+    fn payload_malformed() {
+        let iv = "000102030405060708090a0b0c0d0e0f";
+        let block = "000102030405060708090a0b0c0d0e0f";
+        let cases = vec![
+            String::new(),
+            "0923gnj92bnwio9GJWIFWB".to_string(),
+            format!("{}{}", iv, block),
+            format!("{}:{}:{}", iv, block, block),
+            format!("zz:{}", block),
+            format!("0001:{}", block),
+            format!("{}:", iv),
+            format!("{}:0001", iv),
+            format!("v2:{}:{}", iv, block),
+            format!("vx:{}:{}", iv, block),
+        ];
+
+        for input in cases {
+            assert!(
+                input.parse::<EncryptedPayload>().is_err(),
+                "{:?} should be rejected",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn payload_serde() {
+        #[derive(Serialize, Deserialize)]
+        struct Row {
+            secret: EncryptedPayload,
+        }
+
+        let crypto = Crypto::new("c67106b30d41345119309c05d1c4ab28".to_string());
+        let payload = crypto
+            .encrypt_payload(String::from("halo ini data"))
+            .expect("Failed to encrypt");
+
+        let json = serde_json::to_string(&Row {
+            secret: payload.clone(),
+        })
+        .expect("Failed to serialize");
+        assert_eq!(json, format!(r#"{{"secret":"{}"}}"#, payload));
+
+        let row: Row = serde_json::from_str(&json).expect("Failed to deserialize");
+        assert_eq!(row.secret, payload);
+
+        assert!(serde_json::from_str::<Row>(r#"{"secret":"invalid"}"#).is_err());
+    }
 }
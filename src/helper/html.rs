@@ -0,0 +1,1012 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use super::{display_width, wrap_width};
+use lazy_static::lazy_static;
+use regex::Regex;
+use sanitize_html::rules::pattern::Pattern;
+use sanitize_html::rules::{Element, Rules};
+use sanitize_html::sanitize_str;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Value of the `rel` attribute forced on links by [`SanitizePolicy::nofollow_links`]
+pub const NOFOLLOW_REL: &str = "nofollow noopener";
+
+/// Attributes that contain a URL and are checked against the allowed schemes
+const URL_ATTRIBUTES: [&str; 3] = ["href", "src", "cite"];
+
+/// Block elements that are replaced with a space when they are not allowed
+const SPACE_ELEMENTS: [&str; 29] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "li",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Elements that are always removed together with their content
+const DELETE_ELEMENTS: [&str; 8] = [
+    "iframe", "noembed", "noframes", "noscript", "object", "script", "style", "template",
+];
+
+/// HTML sanitization policy used by [`clean_with`]
+///
+/// Start from one of the profiles, then allow more tags, attributes, or URL schemes.
+/// Tags that are not allowed are removed but their text is kept, and `<script>`, `<style>`, and
+/// similar elements are always removed with their content.
+///
+/// URL attributes (`href`, `src`, `cite`) are only kept if they are relative or use an allowed
+/// scheme, so `javascript:` links are always removed.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::{clean_with, SanitizePolicy};
+///
+/// let policy = SanitizePolicy::basic().allow_tags(&["code"]);
+/// let html = r#"<p>Hello <code>World</code><script>alert(1)</script></p>"#;
+/// assert_eq!(clean_with(html, &policy), "<p>Hello <code>World</code></p>");
+///
+/// let html = r#"<a href="https://portalnesia.com" onclick="alert(1)">Portalnesia</a>"#;
+/// assert_eq!(
+///     clean_with(html, &policy),
+///     r#"<a href="https://portalnesia.com" rel="nofollow noopener">Portalnesia</a>"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanitizePolicy {
+    tags: BTreeMap<String, BTreeSet<String>>,
+    url_schemes: BTreeSet<String>,
+    nofollow: bool,
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        Self::plain_text()
+    }
+}
+
+impl SanitizePolicy {
+    /// Remove every tag, only keep the text. This is the policy used by [`clean`](super::clean)
+    pub fn plain_text() -> Self {
+        SanitizePolicy {
+            tags: BTreeMap::new(),
+            url_schemes: ["http", "https", "mailto"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            nofollow: false,
+        }
+    }
+
+    /// Basic comment formatting: `b`, `i`, `p`, `br`, and `a` with `href` and `title`
+    ///
+    /// Links get `rel="nofollow noopener"`.
+    pub fn basic() -> Self {
+        Self::plain_text()
+            .allow_tags(&["b", "i", "p", "br"])
+            .allow_attributes("a", &["href", "title"])
+            .nofollow_links(true)
+    }
+
+    /// Full article HTML: headings, lists, quotes, code, images, and tables
+    ///
+    /// Links are not forced to `nofollow`, see [`nofollow_links`](Self::nofollow_links).
+    pub fn article() -> Self {
+        Self::basic()
+            .nofollow_links(false)
+            .allow_tags(&[
+                "strong",
+                "em",
+                "u",
+                "s",
+                "del",
+                "ins",
+                "sub",
+                "sup",
+                "small",
+                "mark",
+                "abbr",
+                "code",
+                "pre",
+                "kbd",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "hr",
+                "ul",
+                "li",
+                "dl",
+                "dt",
+                "dd",
+                "figure",
+                "figcaption",
+                "table",
+                "caption",
+                "thead",
+                "tbody",
+                "tfoot",
+                "tr",
+            ])
+            .allow_attributes("abbr", &["title"])
+            .allow_attributes("blockquote", &["cite"])
+            .allow_attributes("q", &["cite"])
+            .allow_attributes("ol", &["start", "reversed", "type"])
+            .allow_attributes("img", &["src", "alt", "title", "width", "height"])
+            .allow_attributes("th", &["colspan", "rowspan", "scope"])
+            .allow_attributes("td", &["colspan", "rowspan"])
+    }
+
+    /// Allow more tags, without attributes
+    pub fn allow_tags(mut self, tags: &[&str]) -> Self {
+        for tag in tags {
+            self.tags.entry(tag.to_ascii_lowercase()).or_default();
+        }
+        self
+    }
+
+    /// Allow attributes on a tag. The tag is allowed too
+    pub fn allow_attributes(mut self, tag: &str, attributes: &[&str]) -> Self {
+        self.tags
+            .entry(tag.to_ascii_lowercase())
+            .or_default()
+            .extend(attributes.iter().map(|a| a.to_ascii_lowercase()));
+        self
+    }
+
+    /// Allow more URL schemes in `href`, `src` and `cite`, e.g. `tel` or `data`
+    ///
+    /// `http`, `https` and `mailto` are allowed by default. Relative URLs are always allowed.
+    pub fn allow_url_schemes(mut self, schemes: &[&str]) -> Self {
+        self.url_schemes.extend(
+            schemes
+                .iter()
+                .map(|s| s.trim_end_matches(':').to_ascii_lowercase()),
+        );
+        self
+    }
+
+    /// Force `rel="nofollow noopener"` on every link, replacing any `rel` from the input
+    pub fn nofollow_links(mut self, nofollow: bool) -> Self {
+        self.nofollow = nofollow;
+        self
+    }
+
+    /// Checks if the tag is allowed
+    pub fn is_tag_allowed(&self, tag: &str) -> bool {
+        self.tags.contains_key(&tag.to_ascii_lowercase())
+    }
+
+    /// Checks if the URL is relative or uses an allowed scheme
+    pub fn is_url_allowed(&self, url: &str) -> bool {
+        url_allowed(&self.url_schemes, url)
+    }
+
+    fn rules(&self) -> Rules {
+        let mut rules = Rules::new();
+
+        for (tag, attributes) in &self.tags {
+            let mut element = Element::new(tag);
+            for attribute in attributes {
+                // `rel` ditimpa oleh nofollow, jangan sampai dobel
+                if self.nofollow && tag == "a" && attribute == "rel" {
+                    continue;
+                }
+                let pattern = if URL_ATTRIBUTES.contains(&attribute.as_str()) {
+                    let schemes = self.url_schemes.clone();
+                    Pattern(Box::new(move |value| url_allowed(&schemes, value)))
+                } else {
+                    Pattern::any()
+                };
+                element = element.attribute(attribute, pattern);
+            }
+            if self.nofollow && tag == "a" {
+                element = element.mandatory_attribute("rel", NOFOLLOW_REL);
+            }
+            rules = rules.element(element);
+        }
+
+        for tag in SPACE_ELEMENTS {
+            if !self.tags.contains_key(tag) {
+                rules = rules.space(tag);
+            }
+        }
+        for tag in DELETE_ELEMENTS {
+            rules = rules.delete(tag);
+        }
+        rules
+    }
+}
+
+fn url_allowed(schemes: &BTreeSet<String>, url: &str) -> bool {
+    let url = url.trim();
+    match url.find(':') {
+        // Titik dua setelah `/`, `?` atau `#` bukan bagian dari scheme
+        Some(pos) if !url[..pos].contains(['/', '?', '#']) => {
+            let scheme: String = url[..pos]
+                .chars()
+                .filter(|c| !c.is_whitespace() && !c.is_control())
+                .collect();
+            schemes.contains(&scheme.to_ascii_lowercase())
+        }
+        _ => true,
+    }
+}
+
+/// Sanitize HTML with the given policy
+///
+/// Leading and trailing whitespace is removed, like [`clean`](super::clean).
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::{clean_with, SanitizePolicy};
+///
+/// let html = r#"<p><b>Hello</b> <img src="javascript:alert(1)"></p>"#;
+/// assert_eq!(clean_with(html, &SanitizePolicy::plain_text()), "Hello");
+/// assert_eq!(clean_with(html, &SanitizePolicy::basic()), "<p><b>Hello</b> </p>");
+/// assert_eq!(clean_with(html, &SanitizePolicy::article()), "<p><b>Hello</b> <img></p>");
+/// ```
+pub fn clean_with(text: &str, policy: &SanitizePolicy) -> String {
+    let cleaned = sanitize_str(&policy.rules(), text).unwrap_or_default();
+
+    lazy_static! {
+        static ref re: Regex = Regex::new(r"^\s+|\s+$").unwrap();
+    }
+
+    re.replace_all(&cleaned, "").to_string()
+}
+
+/// Convert HTML into readable plain text
+///
+/// Paragraphs are separated by a blank line, list items get `- ` or `1. ` bullets, links are
+/// rendered as `text (url)`, and entities are decoded. Lines are wrapped to `width` columns
+/// (display width), or not wrapped at all if `width` is 0.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::html_to_text;
+///
+/// let html = r#"<p>Halo &amp; selamat datang!</p>
+/// <ul><li>Baca <a href="https://portalnesia.com/news">berita</a></li><li>Tulis blog</li></ul>"#;
+/// assert_eq!(
+///     html_to_text(html, 0),
+///     "Halo & selamat datang!\n\n- Baca berita (https://portalnesia.com/news)\n- Tulis blog"
+/// );
+/// ```
+pub fn html_to_text(html: &str, width: usize) -> String {
+    use html5ever::tendril::TendrilSink;
+    use html5ever::{parse_document, ParseOpts};
+    use markup5ever_rcdom::RcDom;
+
+    let dom = parse_document(RcDom::default(), ParseOpts::default()).one(html);
+    let mut renderer = TextRenderer::new(width, true);
+    renderer.walk(&dom.document);
+    renderer.finish()
+}
+
+/// Paragraphs of HTML as plain text, like [`html_to_text`] but without list bullets, quote
+/// markers, horizontal rules, or link URLs, and without wrapping
+pub(crate) fn html_to_paragraphs(html: &str) -> Vec<String> {
+    use html5ever::tendril::TendrilSink;
+    use html5ever::{parse_document, ParseOpts};
+    use markup5ever_rcdom::RcDom;
+
+    let dom = parse_document(RcDom::default(), ParseOpts::default()).one(html);
+    let mut renderer = TextRenderer::new(0, false);
+    renderer.walk(&dom.document);
+    renderer.flush();
+    renderer.blocks.into_iter().map(|b| b.text).collect()
+}
+
+/// One rendered block of text
+struct TextBlock {
+    text: String,
+    /// List items and table rows are not separated by a blank line
+    tight: bool,
+}
+
+struct TextRenderer {
+    width: usize,
+    /// Write list bullets, quote markers, horizontal rules, and link URLs
+    markers: bool,
+    blocks: Vec<TextBlock>,
+    /// Lines of the current paragraph, split by `<br>`
+    lines: Vec<String>,
+    /// Stack of open lists, `Some(counter)` for `<ol>`
+    lists: Vec<Option<usize>>,
+    bullet: Option<String>,
+    quote: usize,
+    pre: usize,
+    row: usize,
+    /// Number of finished paragraphs, a link start is stale once this changes
+    flushes: usize,
+    links: SanitizePolicy,
+}
+
+impl TextRenderer {
+    fn new(width: usize, markers: bool) -> Self {
+        TextRenderer {
+            width,
+            markers,
+            blocks: Vec::new(),
+            lines: vec![String::new()],
+            lists: Vec::new(),
+            bullet: None,
+            quote: 0,
+            pre: 0,
+            row: 0,
+            flushes: 0,
+            links: SanitizePolicy::plain_text(),
+        }
+    }
+
+    fn walk(&mut self, handle: &markup5ever_rcdom::Handle) {
+        use markup5ever_rcdom::NodeData;
+
+        let (name, attrs) = match &handle.data {
+            NodeData::Document => return self.walk_children(handle),
+            NodeData::Text { contents } => return self.push_text(&contents.borrow()),
+            NodeData::Element { name, attrs, .. } => (name, attrs),
+            _ => return,
+        };
+        let attr = |key: &str| {
+            attrs
+                .borrow()
+                .iter()
+                .find(|a| a.name.local.as_ref() == key)
+                .map(|a| a.value.to_string())
+        };
+
+        match name.local.as_ref() {
+            "head" | "script" | "style" | "template" | "noscript" | "iframe" | "object" => {}
+            "br" => self.lines.push(String::new()),
+            "hr" if !self.markers => self.flush(),
+            "hr" => {
+                self.flush();
+                self.blocks.push(TextBlock {
+                    text: "---".to_string(),
+                    tight: false,
+                });
+            }
+            "img" => {
+                if let Some(alt) = attr("alt") {
+                    self.push_text(&alt);
+                }
+            }
+            "a" => {
+                let start = (self.flushes, self.lines.len(), self.current().len());
+                self.walk_children(handle);
+                if let Some(href) = attr("href").filter(|_| self.markers) {
+                    self.push_link(start, href.trim());
+                }
+            }
+            "ul" | "ol" => {
+                self.flush();
+                let counter = if name.local.as_ref() == "ol" {
+                    let start = attr("start").and_then(|s| s.trim().parse::<usize>().ok());
+                    Some(start.unwrap_or(1).saturating_sub(1))
+                } else {
+                    None
+                };
+                self.lists.push(counter);
+                self.walk_children(handle);
+                self.flush();
+                self.lists.pop();
+            }
+            "li" => {
+                self.flush();
+                self.bullet = Some(match self.lists.last_mut() {
+                    Some(Some(counter)) => {
+                        *counter += 1;
+                        format!("{}. ", counter)
+                    }
+                    _ => "- ".to_string(),
+                });
+                self.walk_children(handle);
+                self.flush();
+                self.bullet = None;
+            }
+            "blockquote" => {
+                self.flush();
+                self.quote += 1;
+                self.walk_children(handle);
+                self.flush();
+                self.quote -= 1;
+            }
+            "pre" => {
+                self.flush();
+                self.pre += 1;
+                self.walk_children(handle);
+                self.flush();
+                self.pre -= 1;
+            }
+            "tr" => {
+                self.flush();
+                self.row += 1;
+                self.walk_children(handle);
+                self.flush();
+                self.row -= 1;
+            }
+            "td" | "th" => {
+                let line = self.current();
+                line.truncate(line.trim_end().len());
+                if !line.is_empty() {
+                    line.push_str(" | ");
+                }
+                self.walk_children(handle);
+            }
+            "p" | "div" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "header" | "footer"
+            | "section" | "article" | "aside" | "nav" | "address" | "figure" | "figcaption"
+            | "table" | "caption" | "dl" | "dt" | "dd" => {
+                self.flush();
+                self.walk_children(handle);
+                self.flush();
+            }
+            _ => self.walk_children(handle),
+        }
+    }
+
+    fn walk_children(&mut self, handle: &markup5ever_rcdom::Handle) {
+        for child in handle.children.borrow().iter() {
+            self.walk(child);
+        }
+    }
+
+    fn current(&mut self) -> &mut String {
+        self.lines.last_mut().expect("lines is never empty")
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if self.pre > 0 {
+            let mut parts = text.split('\n');
+            if let Some(first) = parts.next() {
+                self.current().push_str(first);
+            }
+            for part in parts {
+                self.lines.push(part.to_string());
+            }
+            return;
+        }
+
+        // Rapikan spasi seperti browser
+        let line = self.current();
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !line.is_empty() && !line.ends_with(' ') {
+                    line.push(' ');
+                }
+            } else {
+                line.push(c);
+            }
+        }
+    }
+
+    fn push_link(&mut self, (flushes, start_line, start): (usize, usize, usize), href: &str) {
+        if href.is_empty() || href.starts_with('#') || !self.links.is_url_allowed(href) {
+            return;
+        }
+        // Teks link hanya dipakai kalau masih di baris yang sama
+        let text = if flushes == self.flushes && start_line == self.lines.len() {
+            self.current()
+                .get(start..)
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        } else {
+            String::new()
+        };
+        if text == href || href.strip_prefix("mailto:") == Some(text.as_str()) {
+            return;
+        }
+
+        let line = self.current();
+        line.truncate(line.trim_end().len());
+        if text.is_empty() {
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(href);
+        } else {
+            line.push_str(&format!(" ({})", href));
+        }
+    }
+
+    /// Finish the current paragraph and add it as a block
+    fn flush(&mut self) {
+        self.flushes += 1;
+        let lines = std::mem::replace(&mut self.lines, vec![String::new()]);
+        let lines: Vec<String> = if self.pre > 0 {
+            lines
+        } else {
+            lines.iter().map(|l| l.trim().to_string()).collect()
+        };
+        if lines.iter().all(|l| l.trim().is_empty()) {
+            return;
+        }
+
+        let quote = "> ".repeat(self.quote);
+        let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+        let (first, rest) = match self.bullet.take() {
+            _ if !self.markers => (String::new(), String::new()),
+            Some(bullet) => (
+                format!("{}{}{}", quote, indent, bullet),
+                format!("{}{}{}", quote, indent, " ".repeat(bullet.len())),
+            ),
+            None if !self.lists.is_empty() => {
+                let prefix = format!("{}{}  ", quote, indent);
+                (prefix.clone(), prefix)
+            }
+            None => (quote.clone(), quote),
+        };
+
+        let mut out: Vec<String> = Vec::new();
+        for line in &lines {
+            let wrapped = if self.width == 0 || self.pre > 0 {
+                vec![line.clone()]
+            } else {
+                let available = self.width.saturating_sub(display_width(&rest)).max(1);
+                wrap_width(line, available)
+            };
+            for part in wrapped {
+                let prefix = if out.is_empty() { &first } else { &rest };
+                out.push(format!("{}{}", prefix, part).trim_end().to_string());
+            }
+        }
+
+        self.blocks.push(TextBlock {
+            text: out.join("\n"),
+            tight: !self.lists.is_empty() || self.row > 0,
+        });
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        let mut text = String::new();
+        let mut previous_tight = false;
+        for block in &self.blocks {
+            if !text.is_empty() {
+                text.push_str(if previous_tight && block.tight {
+                    "\n"
+                } else {
+                    "\n\n"
+                });
+            }
+            text.push_str(&block.text);
+            previous_tight = block.tight;
+        }
+        text
+    }
+}
+
+/// Elements without closing tag
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Truncate HTML to `max` visible characters, keeping the markup well-formed
+///
+/// Only text counts toward `max`, as grapheme clusters, with a run of whitespace counted once and
+/// whitespace at the start or end of a block not counted, like [`html_to_text`].
+/// Tags that are open at the cut are closed, and tags or entities are never split. Like
+/// [`truncate_with`](super::truncate_with), `max` includes the ellipsis, which is left out if it
+/// does not fit. HTML that already fits is returned unchanged.
+///
+/// The HTML is not sanitized, use [`clean_with`] first for untrusted input.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::truncate_html;
+///
+/// let html = "<p>Halo <b>dunia &amp; semua</b> orang</p>";
+/// assert_eq!(truncate_html(html, 14, "…"), "<p>Halo <b>dunia &amp; …</b></p>");
+/// assert_eq!(truncate_html(html, 100, "…"), html);
+/// ```
+pub fn truncate_html(html: &str, max: usize, ellipsis: &str) -> String {
+    use html5ever::tendril::TendrilSink;
+    use html5ever::{local_name, namespace_url, ns, parse_fragment, ParseOpts, QualName};
+    use markup5ever_rcdom::RcDom;
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let dom = parse_fragment(
+        RcDom::default(),
+        ParseOpts::default(),
+        QualName::new(None, ns!(html), local_name!("body")),
+        Vec::new(),
+    )
+    .one(html);
+    // Hasil parse_fragment dibungkus satu elemen <html>
+    let Some(root) = dom.document.children.borrow().first().cloned() else {
+        return String::new();
+    };
+
+    let mut counter = HtmlTruncator::new(usize::MAX, "");
+    counter.write_children(&root);
+    if usize::MAX - counter.remaining <= max {
+        return html.to_string();
+    }
+
+    let ellipsis_len = ellipsis.graphemes(true).count();
+    let (keep, ellipsis) = if ellipsis_len <= max {
+        (max - ellipsis_len, ellipsis)
+    } else {
+        (max, "")
+    };
+    let mut truncator = HtmlTruncator::new(keep, ellipsis);
+    truncator.write_children(&root);
+    truncator.out
+}
+
+struct HtmlTruncator<'a> {
+    remaining: usize,
+    ellipsis: &'a str,
+    out: String,
+    /// Whitespace since the last visible character, not written yet
+    pending_space: String,
+    /// Whitespace since the last visible character, only counted if more text follows
+    space_owed: bool,
+    /// No visible character yet in the current block
+    block_start: bool,
+    done: bool,
+}
+
+impl<'a> HtmlTruncator<'a> {
+    fn new(remaining: usize, ellipsis: &'a str) -> Self {
+        HtmlTruncator {
+            remaining,
+            ellipsis,
+            out: String::new(),
+            pending_space: String::new(),
+            space_owed: false,
+            block_start: true,
+            done: false,
+        }
+    }
+
+    fn write_children(&mut self, handle: &markup5ever_rcdom::Handle) {
+        for child in handle.children.borrow().iter() {
+            if self.done {
+                break;
+            }
+            self.write(child);
+        }
+    }
+
+    fn write(&mut self, handle: &markup5ever_rcdom::Handle) {
+        use markup5ever_rcdom::NodeData;
+        use unicode_segmentation::UnicodeSegmentation;
+
+        match &handle.data {
+            NodeData::Text { contents } => {
+                for grapheme in contents.borrow().graphemes(true) {
+                    // Spasi di awal blok tidak terlihat, seperti di html_to_text
+                    if grapheme.trim().is_empty() {
+                        if self.block_start {
+                            push_escaped(&mut self.out, grapheme, false);
+                        } else {
+                            self.pending_space.push_str(grapheme);
+                            self.space_owed = true;
+                        }
+                        continue;
+                    }
+
+                    let space = usize::from(self.space_owed);
+                    if space + 1 > self.remaining {
+                        if space > 0 && self.remaining > 0 {
+                            push_escaped(&mut self.out, &self.pending_space, false);
+                        }
+                        self.pending_space.clear();
+                        self.out.push_str(self.ellipsis);
+                        self.done = true;
+                        return;
+                    }
+                    self.remaining -= space + 1;
+                    push_escaped(&mut self.out, &self.pending_space, false);
+                    self.pending_space.clear();
+                    self.space_owed = false;
+                    self.block_start = false;
+                    push_escaped(&mut self.out, grapheme, false);
+                }
+            }
+            NodeData::Element { name, attrs, .. } => {
+                let tag = name.local.as_ref();
+                let block = SPACE_ELEMENTS.contains(&tag);
+                if block {
+                    self.end_block();
+                } else if self.space_owed {
+                    // Spasi sebelum tag inline tetap terlihat
+                    if self.remaining == 0 {
+                        self.pending_space.clear();
+                        self.out.push_str(self.ellipsis);
+                        self.done = true;
+                        return;
+                    }
+                    self.remaining -= 1;
+                    self.space_owed = false;
+                    push_escaped(&mut self.out, &self.pending_space, false);
+                    self.pending_space.clear();
+                }
+                self.out.push('<');
+                self.out.push_str(tag);
+                for attr in attrs.borrow().iter() {
+                    self.out.push(' ');
+                    self.out.push_str(attr.name.local.as_ref());
+                    self.out.push_str("=\"");
+                    push_escaped(&mut self.out, &attr.value, true);
+                    self.out.push('"');
+                }
+                self.out.push('>');
+                if VOID_ELEMENTS.contains(&tag) {
+                    return;
+                }
+
+                if tag == "script" || tag == "style" {
+                    // Isi script dan style tidak terlihat, tulis apa adanya
+                    for child in handle.children.borrow().iter() {
+                        if let NodeData::Text { contents } = &child.data {
+                            self.out.push_str(&contents.borrow());
+                        }
+                    }
+                } else {
+                    self.write_children(handle);
+                }
+                if block {
+                    self.end_block();
+                }
+                self.out.push_str("</");
+                self.out.push_str(tag);
+                self.out.push('>');
+            }
+            NodeData::Comment { contents } => {
+                self.out.push_str("<!--");
+                self.out.push_str(contents);
+                self.out.push_str("-->");
+            }
+            _ => {}
+        }
+    }
+}
+
+impl HtmlTruncator<'_> {
+    /// Whitespace at the end of a block is not visible, write it without counting
+    fn end_block(&mut self) {
+        push_escaped(&mut self.out, &self.pending_space, false);
+        self.pending_space.clear();
+        self.space_owed = false;
+        self.block_start = true;
+    }
+}
+
+fn push_escaped(out: &mut String, text: &str, attribute: bool) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '\u{a0}' => out.push_str("&nbsp;"),
+            '"' if attribute => out.push_str("&quot;"),
+            '<' if !attribute => out.push_str("&lt;"),
+            '>' if !attribute => out.push_str("&gt;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_with_profiles() {
+        let html = r#"<h1>Title</h1><p>Hello <b>bold</b> <strong>strong</strong> <a href="https://portalnesia.com" rel="author">link</a></p><img src="https://portalnesia.com/a.png" alt="A"><table><tr><td colspan="2">cell</td></tr></table><script>alert(1)</script>"#;
+        let cases = vec![
+            (
+                SanitizePolicy::plain_text(),
+                "Title  Hello bold strong link  cell",
+            ),
+            (
+                SanitizePolicy::basic(),
+                r#"Title <p>Hello <b>bold</b> strong <a href="https://portalnesia.com" rel="nofollow noopener">link</a></p> cell"#,
+            ),
+            (
+                SanitizePolicy::article(),
+                r#"<h1>Title</h1><p>Hello <b>bold</b> <strong>strong</strong> <a href="https://portalnesia.com">link</a></p><img src="https://portalnesia.com/a.png" alt="A"><table><tbody><tr><td colspan="2">cell</td></tr></tbody></table>"#,
+            ),
+            (
+                SanitizePolicy::article()
+                    .allow_attributes("a", &["rel"])
+                    .nofollow_links(true),
+                r#"<h1>Title</h1><p>Hello <b>bold</b> <strong>strong</strong> <a href="https://portalnesia.com" rel="nofollow noopener">link</a></p><img src="https://portalnesia.com/a.png" alt="A"><table><tbody><tr><td colspan="2">cell</td></tr></tbody></table>"#,
+            ),
+        ];
+        for (policy, expected) in cases {
+            let got = clean_with(html, &policy);
+            assert_eq!(
+                got, expected,
+                "clean_with({:?}) should be {:?}, got {:?}",
+                policy, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_url_schemes() {
+        let policy = SanitizePolicy::basic().allow_url_schemes(&["tel:"]);
+        let cases = vec![
+            ("https://portalnesia.com", true),
+            ("mailto:support@portalnesia.com", true),
+            ("tel:+62123", true),
+            ("/relative/path:with-colon", true),
+            ("?q=a:b", true),
+            ("#top", true),
+            ("javascript:alert(1)", false),
+            (" JavaScript :alert(1)", false),
+            ("java\tscript:alert(1)", false),
+            ("data:text/html;base64,PHNjcmlwdD4=", false),
+        ];
+        for (input, expected) in cases {
+            let got = policy.is_url_allowed(input);
+            assert_eq!(
+                got, expected,
+                "is_url_allowed({:?}) should be {:?}, got {:?}",
+                input, expected, got
+            );
+        }
+
+        let html = r#"<a href="javascript&#58;alert(1)">a</a><a href="tel:+62123">b</a>"#;
+        assert_eq!(
+            clean_with(html, &policy),
+            r#"<a rel="nofollow noopener">a</a><a href="tel:+62123" rel="nofollow noopener">b</a>"#
+        );
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        let cases = vec![
+            ("", 0, ""),
+            (
+                "<p>Hello   <b>World</b></p><p>Second\n line</p>",
+                0,
+                "Hello World\n\nSecond line",
+            ),
+            ("Line<br>break", 0, "Line\nbreak"),
+            (
+                "<p>Tom &amp; Jerry &lt;3 &eacute;</p>",
+                0,
+                "Tom & Jerry <3 é",
+            ),
+            (
+                "<ol start=\"3\"><li>Three</li><li>Four<ul><li>Nested</li></ul></li></ol>",
+                0,
+                "3. Three\n4. Four\n  - Nested",
+            ),
+            (
+                r#"<a href="https://portalnesia.com">Portalnesia</a> <a href="https://portalnesia.com">https://portalnesia.com</a>"#,
+                0,
+                "Portalnesia (https://portalnesia.com) https://portalnesia.com",
+            ),
+            (
+                r##"<a href="mailto:a@b.c">a@b.c</a> <a href="javascript:alert(1)">x</a> <a href="#top">top</a>"##,
+                0,
+                "a@b.c x top",
+            ),
+            ("<blockquote><p>Quote</p></blockquote>", 0, "> Quote"),
+            (
+                "<pre>fn main() {\n    code();\n}</pre>",
+                0,
+                "fn main() {\n    code();\n}",
+            ),
+            (
+                "<table><tr><th>A</th><th>B</th></tr><tr><td>1</td><td>2</td></tr></table>",
+                0,
+                "A | B\n1 | 2",
+            ),
+            (
+                "<style>p{}</style><script>x()</script><p>Text</p><hr><img alt=\"Logo\">",
+                0,
+                "Text\n\n---\n\nLogo",
+            ),
+            (
+                "<p>Selamat datang di Portalnesia</p><ul><li>Satu dua tiga empat</li></ul>",
+                12,
+                "Selamat\ndatang di\nPortalnesia\n\n- Satu dua\n  tiga empat",
+            ),
+            // Blok di dalam link tidak boleh bikin panic
+            (
+                r#"Baca <a href="https://x.com"><p>berita</p></a>"#,
+                0,
+                "Baca\n\nberita\n\nhttps://x.com",
+            ),
+            (
+                r#"xy<a href="https://x.com"><div>x</div>€</a>"#,
+                0,
+                "xy\n\nx\n\n€ https://x.com",
+            ),
+        ];
+        for (input, width, expected) in cases {
+            let got = html_to_text(input, width);
+            assert_eq!(
+                got, expected,
+                "html_to_text({:?}, {}) should be {:?}, got {:?}",
+                input, width, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncate_html() {
+        let cases = vec![
+            ("", 5, ""),
+            ("<p>Hello</p>", 5, "<p>Hello</p>"),
+            ("<p>Hello World</p>", 8, "<p>Hello...</p>"),
+            (
+                "<div><p>One <i>two</i></p><p>three</p></div>",
+                9,
+                "<div><p>One <i>tw...</i></p></div>",
+            ),
+            ("<p>a &lt;b&gt; c</p>", 6, "<p>a &lt;...</p>"),
+            ("<p>Tom &amp; Jerry</p>", 4, "<p>T...</p>"),
+            (
+                "<p>Hello    World   again</p>",
+                15,
+                "<p>Hello    World   ...</p>",
+            ),
+            ("<p>Ko<br>☕☕☕☕☕</p>", 6, "<p>Ko<br>☕...</p>"),
+            (
+                r#"<a href="/a?x=1&amp;y=&quot;2&quot;">Link text</a><img src="x.png">"#,
+                5,
+                r#"<a href="/a?x=1&amp;y=&quot;2&quot;">Li...</a>"#,
+            ),
+            ("<p>Hello <b>World", 8, "<p>Hello...</p>"),
+            ("<p>Hello World</p>", 2, "<p>He</p>"),
+            // Spasi di antara blok tidak dihitung
+            ("<p>  </p><p>ab</p>", 2, "<p>  </p><p>ab</p>"),
+            ("<p>  </p><p>abc</p>", 2, "<p>  </p><p>ab</p>"),
+            ("<p> a </p>\n<p> bc </p>", 3, "<p> a </p>\n<p> bc </p>"),
+        ];
+        for (input, max, expected) in cases {
+            let got = truncate_html(input, max, "...");
+            assert_eq!(
+                got, expected,
+                "truncate_html({:?}, {}) should be {:?}, got {:?}",
+                input, max, expected, got
+            );
+        }
+        assert_eq!(
+            truncate_html("<p>  </p><p>ab</p>", 1, "…"),
+            "<p>  </p><p>…</p>"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use super::{clean_with, html_to_paragraphs, html_to_text, truncate_words, SanitizePolicy};
+use pulldown_cmark::{html, Options, Parser};
+
+/// Render Markdown into unsanitized HTML
+fn render_markdown(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let mut output = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut output, Parser::new_ext(markdown, options));
+    output
+}
+
+/// Render Markdown into sanitized HTML
+///
+/// Supports CommonMark, tables, and `~~strikethrough~~`. The result, including any raw HTML in
+/// the Markdown, is sanitized with the given policy, see [`clean_with`].
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::{markdown_to_html, SanitizePolicy};
+///
+/// let markdown = "**Halo** [Portalnesia](https://portalnesia.com) <script>alert(1)</script>";
+/// assert_eq!(
+///     markdown_to_html(markdown, &SanitizePolicy::basic()),
+///     r#"<p><b>Halo</b> <a href="https://portalnesia.com" rel="nofollow noopener">Portalnesia</a> </p>"#
+/// );
+/// ```
+pub fn markdown_to_html(markdown: &str, policy: &SanitizePolicy) -> String {
+    let html = render_markdown(markdown);
+    // `strong` dan `em` diubah ke `b` dan `i` kalau policy hanya mengizinkan versi pendeknya
+    let html = if !policy.is_tag_allowed("strong") && policy.is_tag_allowed("b") {
+        html.replace("<strong>", "<b>").replace("</strong>", "</b>")
+    } else {
+        html
+    };
+    let html = if !policy.is_tag_allowed("em") && policy.is_tag_allowed("i") {
+        html.replace("<em>", "<i>").replace("</em>", "</i>")
+    } else {
+        html
+    };
+    clean_with(&html, policy)
+}
+
+/// Render Markdown into readable plain text, see [`html_to_text`]
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::markdown_to_text;
+///
+/// let markdown = "# Judul\n\nBaca [berita](https://portalnesia.com/news).\n\n* Satu\n* Dua";
+/// assert_eq!(
+///     markdown_to_text(markdown, 0),
+///     "Judul\n\nBaca berita (https://portalnesia.com/news).\n\n- Satu\n- Dua"
+/// );
+/// ```
+pub fn markdown_to_text(markdown: &str, width: usize) -> String {
+    html_to_text(&render_markdown(markdown), width)
+}
+
+/// Plain-text excerpt of Markdown, on one line and cut at a word boundary
+///
+/// Entities are decoded and links keep only their text. `max` is the number of characters
+/// including the `...`, see [`truncate_words`].
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::markdown_excerpt;
+///
+/// let markdown = "# Judul\n\nIni adalah **isi** artikel yang panjang.";
+/// assert_eq!(markdown_excerpt(markdown, 30), "Judul Ini adalah isi...");
+/// ```
+pub fn markdown_excerpt(markdown: &str, max: usize) -> String {
+    let text = html_to_paragraphs(&render_markdown(markdown)).join(" ");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    truncate_words(&text, max, "...")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_html() {
+        let cases = vec![
+            (
+                "Hello ~~old~~ *new* [x](javascript:alert(1))",
+                SanitizePolicy::article(),
+                r#"<p>Hello <del>old</del> <em>new</em> <a>x</a></p>"#,
+            ),
+            (
+                "Hello ~~old~~ *new*",
+                SanitizePolicy::basic(),
+                r#"<p>Hello old <i>new</i></p>"#,
+            ),
+            (
+                "| A | B |\n|---|:-:|\n| 1 | 2 |",
+                SanitizePolicy::article(),
+                "<table><thead><tr><th>A</th><th>B</th></tr></thead><tbody>\n<tr><td>1</td><td>2</td></tr>\n</tbody></table>",
+            ),
+            (
+                "![logo](https://portalnesia.com/logo.png \"Logo\")\n\n<img src=x onerror=alert(1)>",
+                SanitizePolicy::article(),
+                "<p><img src=\"https://portalnesia.com/logo.png\" alt=\"logo\" title=\"Logo\"></p>\n<img src=\"x\">",
+            ),
+            ("# Title\n\ntext", SanitizePolicy::plain_text(), "Title \n text"),
+        ];
+        for (input, policy, expected) in cases {
+            let got = markdown_to_html(input, &policy);
+            assert_eq!(
+                got, expected,
+                "markdown_to_html({:?}) should be {:?}, got {:?}",
+                input, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_markdown_excerpt() {
+        let cases = vec![
+            ("", 10, ""),
+            ("Short", 10, "Short"),
+            (
+                "A **bold** _move_\n\n- one\n- two",
+                100,
+                "A bold move one two",
+            ),
+            ("Satu dua tiga empat lima", 15, "Satu dua..."),
+            // Entity harus didecode dulu
+            (
+                "Tom & Jerry <3 \"quoted\"",
+                100,
+                "Tom & Jerry <3 \"quoted\"",
+            ),
+            ("a\u{a0}b", 100, "a b"),
+            ("Tom & Jerry", 8, "Tom..."),
+            (
+                "Baca [berita](https://portalnesia.com) ini",
+                100,
+                "Baca berita ini",
+            ),
+        ];
+        for (input, max, expected) in cases {
+            let got = markdown_excerpt(input, max);
+            assert_eq!(
+                got, expected,
+                "markdown_excerpt({:?}, {}) should be {:?}, got {:?}",
+                input, max, expected, got
+            );
+        }
+    }
+}
@@ -6,9 +6,15 @@
  */
 
 mod bool;
+mod html;
+mod markdown;
 mod number;
 mod string;
+mod summary;
 
 pub use bool::*;
+pub use html::*;
+pub use markdown::*;
 pub use number::*;
 pub use string::*;
+pub use summary::*;
@@ -5,11 +5,15 @@
  * Written by Putu Aditya <aditya@portalnesia.com>
  */
 
+use super::{clean_with, SanitizePolicy};
 use regex::Regex;
 use url::{ParseError, Url};
 
 /// Truncate string
 ///
+/// `max` is the number of grapheme clusters (user-perceived characters) including the `...`,
+/// so emoji and accented characters are never split. See [`truncate_with`] for another ellipsis.
+///
 /// ## Example
 ///
 /// ```
@@ -17,17 +21,360 @@ use url::{ParseError, Url};
 /// // result == lorem ipsum lor...
 /// ```
 pub fn truncate(text: String, max: usize) -> String {
-    if max > text.len() {
-        text
+    truncate_with(&text, max, "...")
+}
+
+/// Truncate string with a custom ellipsis
+///
+/// Counts grapheme clusters, so `"👨‍👩‍👧"` or `"é"` written as `e` + accent count as one character.
+/// The result is never longer than `max` graphemes: if the ellipsis itself does not fit, it is
+/// left out. Never panics.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::truncate_with;
+///
+/// assert_eq!(truncate_with("Selamat pagi 🌞🌞🌞", 14, "…"), "Selamat pagi …");
+/// assert_eq!(truncate_with("Kopi ☕", 10, "…"), "Kopi ☕");
+/// assert_eq!(truncate_with("Kopi ☕", 2, "..."), "Ko");
+/// ```
+pub fn truncate_with(text: &str, max: usize, ellipsis: &str) -> String {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    if text.graphemes(true).nth(max).is_none() {
+        return text.to_string();
+    }
+
+    let ellipsis_len = ellipsis.graphemes(true).count();
+    let (keep, ellipsis) = if ellipsis_len <= max {
+        (max - ellipsis_len, ellipsis)
+    } else {
+        // Ellipsis tidak muat, potong teksnya saja
+        (max, "")
+    };
+
+    let end = text
+        .grapheme_indices(true)
+        .nth(keep)
+        .map_or(text.len(), |(i, _)| i);
+    format!("{}{}", &text[..end], ellipsis)
+}
+
+/// Truncate string at a word boundary
+///
+/// Backs off to the last whitespace or punctuation so no word is cut in half, then adds `suffix`.
+/// `max` counts grapheme clusters including the suffix. See [`TruncateOptions`] for a byte limit.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::truncate_words;
+///
+/// let result = truncate_words("lorem ipsum lorem ipsum lorem ipsum", 18, "...");
+/// assert_eq!(result, "lorem ipsum...");
+///
+/// let result = truncate_words("Berita terbaru hari ini", 20, " [more]");
+/// assert_eq!(result, "Berita [more]");
+/// ```
+pub fn truncate_words(text: &str, max: usize, suffix: &str) -> String {
+    TruncateOptions::new(max)
+        .suffix(suffix)
+        .word_boundary(true)
+        .apply(text)
+}
+
+/// Options for truncating text
+///
+/// Default: no word boundary, `...` as suffix and no byte limit.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::TruncateOptions;
+///
+/// // Fits a VARCHAR(32) column, counted in bytes
+/// let title = TruncateOptions::new(100)
+///     .suffix("…")
+///     .word_boundary(true)
+///     .max_bytes(32)
+///     .apply("Résumé lengkap pertandingan sepak bola semalam");
+/// assert_eq!(title, "Résumé lengkap pertandingan…");
+/// assert!(title.len() <= 32);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruncateOptions {
+    max: usize,
+    suffix: String,
+    word_boundary: bool,
+    max_bytes: Option<usize>,
+}
+
+impl TruncateOptions {
+    /// Create options with the maximum number of grapheme clusters, including the suffix
+    pub fn new(max: usize) -> Self {
+        TruncateOptions {
+            max,
+            suffix: "...".to_string(),
+            word_boundary: false,
+            max_bytes: None,
+        }
+    }
+
+    /// Added to the end of truncated text. Default: `...`
+    pub fn suffix(mut self, suffix: &str) -> Self {
+        self.suffix = suffix.to_string();
+        self
+    }
+
+    /// Back off to the last whitespace or punctuation. Default: `false`
+    pub fn word_boundary(mut self, enabled: bool) -> Self {
+        self.word_boundary = enabled;
+        self
+    }
+
+    /// Never exceed this length in UTF-8 bytes, including the suffix
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Truncate the text. Never panics
+    pub fn apply(&self, text: &str) -> String {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let max_bytes = self.max_bytes.unwrap_or(usize::MAX);
+        if text.len() <= max_bytes && text.graphemes(true).nth(self.max).is_none() {
+            return text.to_string();
+        }
+
+        let suffix_len = self.suffix.graphemes(true).count();
+        let (max, max_bytes, suffix) = if suffix_len <= self.max && self.suffix.len() <= max_bytes {
+            (
+                self.max - suffix_len,
+                max_bytes - self.suffix.len(),
+                self.suffix.as_str(),
+            )
+        } else {
+            // Suffix tidak muat, potong teksnya saja
+            (self.max, max_bytes, "")
+        };
+
+        // Batas terakhir yang masih muat, dihitung per grapheme
+        let mut end = 0;
+        for (count, (i, grapheme)) in text.grapheme_indices(true).enumerate() {
+            if count >= max || i + grapheme.len() > max_bytes {
+                break;
+            }
+            end = i + grapheme.len();
+        }
+
+        let mut truncated = &text[..end];
+        if self.word_boundary && !is_word_boundary_at(text, end) {
+            if let Some((boundary, _)) = truncated
+                .char_indices()
+                .rev()
+                .find(|(i, _)| is_word_boundary_at(text, *i))
+            {
+                truncated = &truncated[..boundary];
+            }
+        }
+        if self.word_boundary {
+            while let Some((i, _)) = truncated.char_indices().next_back() {
+                if !is_word_boundary_at(text, i) {
+                    break;
+                }
+                truncated = &truncated[..i];
+            }
+        }
+
+        format!("{}{}", truncated, suffix)
+    }
+}
+
+/// Whitespace and punctuation where text can be cut
+fn is_word_boundary(c: char) -> bool {
+    c.is_whitespace() || c.is_ascii_punctuation() || matches!(c, '…' | '–' | '—' | '。' | '、')
+}
+
+/// Checks if the character at byte index `i` is a word boundary
+///
+/// Apostrophes and hyphens inside a word, like "don't" or "kupu-kupu", are not boundaries.
+fn is_word_boundary_at(text: &str, i: usize) -> bool {
+    let Some(c) = text[i..].chars().next() else {
+        return false;
+    };
+    if !is_word_boundary(c) {
+        return false;
+    }
+    if matches!(c, '\'' | '’' | '-' | '‐') {
+        let before = text[..i].chars().next_back();
+        let after = text[i + c.len_utf8()..].chars().next();
+        if before.is_some_and(char::is_alphanumeric) && after.is_some_and(char::is_alphanumeric) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Text alignment for [`pad_width`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    /// Pad on the right, the default
+    #[default]
+    Left,
+    /// Pad on the left
+    Right,
+    /// Pad on both sides, the extra column goes to the right
+    Center,
+}
+
+/// Number of terminal columns used by the text
+///
+/// CJK and most emoji use 2 columns, combining marks and zero-width characters use 0.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::display_width;
+///
+/// assert_eq!(display_width("abc"), 3);
+/// assert_eq!(display_width("日本語"), 6);
+/// assert_eq!(display_width("e\u{301}"), 1);
+/// ```
+pub fn display_width(text: &str) -> usize {
+    use unicode_width::UnicodeWidthStr;
+
+    text.width()
+}
+
+/// Truncate string to a terminal column width, including the ellipsis
+///
+/// Graphemes are never split. If a 2-column character does not fit, the result is one column
+/// shorter. If the ellipsis itself does not fit, it is left out.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::truncate_width;
+///
+/// assert_eq!(truncate_width("東京タワー", 7, "…"), "東京タ…");
+/// assert_eq!(truncate_width("東京タワー", 6, "…"), "東京…");
+/// assert_eq!(truncate_width("Jakarta", 10, "…"), "Jakarta");
+/// ```
+pub fn truncate_width(text: &str, max_width: usize, ellipsis: &str) -> String {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+
+    let ellipsis_width = display_width(ellipsis);
+    let (budget, ellipsis) = if ellipsis_width <= max_width {
+        (max_width - ellipsis_width, ellipsis)
     } else {
-        let max_size = max - 3;
-        let str = format!("{}...", &text[..max_size]);
-        str
+        (max_width, "")
+    };
+
+    let mut width = 0;
+    let mut end = 0;
+    for (i, grapheme) in text.grapheme_indices(true) {
+        width += display_width(grapheme);
+        if width > budget {
+            break;
+        }
+        end = i + grapheme.len();
     }
+    format!("{}{}", &text[..end], ellipsis)
+}
+
+/// Pad string with spaces to a terminal column width
+///
+/// Text that is already wider is returned as is, use [`truncate_width`] first to cut it.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::{pad_width, Align};
+///
+/// assert_eq!(pad_width("日本", 6, Align::Left), "日本  ");
+/// assert_eq!(pad_width("42", 5, Align::Right), "   42");
+/// assert_eq!(pad_width("ok", 5, Align::Center), " ok  ");
+/// ```
+pub fn pad_width(text: &str, width: usize, align: Align) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    let (left, right) = match align {
+        Align::Left => (0, padding),
+        Align::Right => (padding, 0),
+        // Sisa ganjil ditaruh di kanan
+        Align::Center => (padding / 2, padding - padding / 2),
+    };
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
+
+/// Wrap text into lines of at most `width` terminal columns
+///
+/// Lines break at whitespace, and words wider than `width` are split between graphemes.
+/// Existing line breaks are kept. A line always has at least one grapheme, so a 2-column
+/// character is never dropped when `width` is 1.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::wrap_width;
+///
+/// assert_eq!(
+///     wrap_width("Selamat datang di Portalnesia", 12),
+///     vec!["Selamat", "datang di", "Portalnesia"]
+/// );
+/// assert_eq!(wrap_width("日本語のテキスト", 6), vec!["日本語", "のテキ", "スト"]);
+/// ```
+pub fn wrap_width(text: &str, width: usize) -> Vec<String> {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut line_width = 0;
+
+        for word in paragraph.split_whitespace() {
+            let word_width = display_width(word);
+            if line_width > 0 && line_width + 1 + word_width <= width {
+                line.push(' ');
+                line.push_str(word);
+                line_width += 1 + word_width;
+                continue;
+            }
+            if line_width > 0 {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            if word_width <= width {
+                line.push_str(word);
+                line_width = word_width;
+                continue;
+            }
+
+            // Kata lebih panjang dari satu baris, potong per grapheme
+            for grapheme in word.graphemes(true) {
+                let grapheme_width = display_width(grapheme);
+                if line_width > 0 && line_width + grapheme_width > width {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                line.push_str(grapheme);
+                line_width += grapheme_width;
+            }
+        }
+        lines.push(line);
+    }
+    lines
 }
 
 /// Clean string into unformatted HTML
 ///
+/// Removes every tag, see [`clean_with`] to keep some formatting.
+///
 /// ## Example
 ///
 /// ```
@@ -35,18 +382,7 @@ pub fn truncate(text: String, max: usize) -> String {
 /// // result == "XSS"
 ///```
 pub fn clean(text: String) -> String {
-    use lazy_static::lazy_static;
-    use regex::Regex;
-    use sanitize_html::rules::predefined::DEFAULT;
-    use sanitize_html::sanitize_str;
-
-    let cleaned = sanitize_str(&DEFAULT, text.as_str()).unwrap_or("".to_string());
-
-    lazy_static! {
-        static ref re: Regex = Regex::new(r"^\s+|\s+$").unwrap();
-    }
-
-    re.replace_all(&cleaned, "").to_string()
+    clean_with(&text, &SanitizePolicy::plain_text())
 }
 
 /// Clean text data and truncate
@@ -252,25 +588,331 @@ macro_rules! first_letter {
 
 /// Slug format string to slugify
 ///
+/// Keeps any alphanumeric character, see [`SlugOptions`] for an ASCII-only slug.
+///
 /// Example: "hello world" => "hello-world"
 pub fn slug(input: &str) -> String {
-    input
-        .to_lowercase()
-        .chars()
-        .filter_map(|c| {
-            if c.is_alphanumeric() {
-                Some(c)
-            } else if c.is_whitespace() || c == '-' || c == '_' {
-                Some('-')
+    SlugOptions::new().apply(input)
+}
+
+/// Common English stop-words, for [`SlugOptions::stop_words`]
+pub const STOP_WORDS_EN: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is", "of", "on", "or",
+    "the", "to", "with",
+];
+
+/// Common Indonesian stop-words, for [`SlugOptions::stop_words`]
+pub const STOP_WORDS_ID: &[&str] = &[
+    "adalah", "atau", "dan", "dari", "dengan", "di", "ini", "itu", "ke", "oleh", "pada", "untuk",
+    "yang",
+];
+
+/// Options to build a slug
+///
+/// With the default options the result is the same as [`slug`].
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::{SlugOptions, STOP_WORDS_ID};
+///
+/// let options = SlugOptions::new().transliterate(true);
+/// assert_eq!(options.apply("Café Über"), "cafe-uber");
+/// assert_eq!(options.apply("北京欢迎你"), "bei-jing-huan-ying-ni");
+///
+/// let options = SlugOptions::new()
+///     .transliterate(true)
+///     .separator("_")
+///     .stop_words(STOP_WORDS_ID)
+///     .max_length(24);
+/// assert_eq!(options.apply("Cara Membuat Kopi yang Enak di Rumah"), "cara_membuat_kopi_enak");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlugOptions {
+    transliterate: bool,
+    separator: String,
+    max_length: Option<usize>,
+    stop_words: Vec<String>,
+}
+
+impl Default for SlugOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SlugOptions {
+    /// Create options with `-` as separator, without transliteration or limits
+    pub fn new() -> Self {
+        SlugOptions {
+            transliterate: false,
+            separator: "-".to_string(),
+            max_length: None,
+            stop_words: Vec::new(),
+        }
+    }
+
+    /// Transliterate into ASCII, e.g. `é` to `e`, `ß` to `ss`, and `北京` to `bei-jing`.
+    /// Symbols and emoji are removed. Default: `false`
+    pub fn transliterate(mut self, enabled: bool) -> Self {
+        self.transliterate = enabled;
+        self
+    }
+
+    /// Separator between words. Default: `-`
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Maximum length in characters, cut at a word boundary
+    ///
+    /// A first word longer than the limit is cut in the middle.
+    pub fn max_length(mut self, max: usize) -> Self {
+        self.max_length = Some(max);
+        self
+    }
+
+    /// Remove these words, e.g. [`STOP_WORDS_EN`] or [`STOP_WORDS_ID`]
+    ///
+    /// Can be called several times. Stop-words are kept if the slug would be empty without them.
+    pub fn stop_words(mut self, words: &[&str]) -> Self {
+        self.stop_words
+            .extend(words.iter().map(|w| w.to_lowercase()));
+        self
+    }
+
+    /// Create the slug
+    pub fn apply(&self, input: &str) -> String {
+        let mut text = String::with_capacity(input.len());
+        for c in input.chars() {
+            if c.is_whitespace() || c == '-' || c == '_' {
+                text.push(' ');
+            } else if !c.is_alphanumeric() {
+                continue;
+            } else if !self.transliterate || c.is_ascii() {
+                text.extend(c.to_lowercase());
+            } else if let Some(ascii) = deunicode::deunicode_char(c) {
+                // Transliterasi CJK diakhiri spasi, jadi tiap suku kata menjadi kata sendiri
+                text.extend(
+                    ascii
+                        .chars()
+                        .filter(|c| c.is_ascii_alphanumeric() || *c == ' ')
+                        .map(|c| c.to_ascii_lowercase()),
+                );
+            }
+        }
+
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let filtered: Vec<&str> = words
+            .iter()
+            .copied()
+            .filter(|w| !self.stop_words.iter().any(|s| s == w))
+            .collect();
+        let words = if filtered.is_empty() { words } else { filtered };
+
+        let Some(max) = self.max_length else {
+            return words.join(&self.separator);
+        };
+        let separator_len = self.separator.chars().count();
+        let mut result = String::new();
+        let mut length = 0;
+        for word in words {
+            let word_len = word.chars().count();
+            if length == 0 {
+                if word_len > max {
+                    // Kata pertama terlalu panjang, potong di tengah
+                    return word.chars().take(max).collect();
+                }
+                result.push_str(word);
+                length = word_len;
+            } else if length + separator_len + word_len <= max {
+                result.push_str(&self.separator);
+                result.push_str(word);
+                length += separator_len + word_len;
             } else {
-                None
+                break;
             }
-        })
-        .collect::<String>()
-        .split('-')
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .join("-")
+        }
+        result
+    }
+}
+
+/// Incrementing suffixes tried before switching to random suffixes
+const MAX_INCREMENT_ATTEMPTS: usize = 100;
+
+/// Length of random suffixes used by [`SlugSuffix::Increment`]
+const RANDOM_SUFFIX_LENGTH: usize = 8;
+
+/// Suffix added by [`UniqueSlugOptions`] when the slug is taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SlugSuffix {
+    /// `title-2`, `title-3`, ... After 100 attempts random suffixes of 8 characters are used
+    #[default]
+    Increment,
+    /// Random lowercase nanoid with the given length, e.g. `title-x8k2p`
+    ///
+    /// Shortened if it does not fit in the maximum length of the slug.
+    Random(usize),
+}
+
+/// Options to build a slug that does not exist yet
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::{SlugOptions, SlugSuffix, UniqueSlugOptions};
+///
+/// let taken = ["kopi-enak", "kopi-enak-2"];
+/// let options = UniqueSlugOptions::new();
+/// assert_eq!(options.generate("Kopi Enak", |s| taken.contains(&s)), "kopi-enak-3");
+///
+/// let options = UniqueSlugOptions::new()
+///     .slug_options(SlugOptions::new().transliterate(true).max_length(12))
+///     .suffix(SlugSuffix::Random(4));
+/// let slug = options.generate("Café Enak Sekali", |s| s == "cafe-enak");
+/// assert!(slug.starts_with("cafe-"));
+/// assert!(slug.len() <= 12);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UniqueSlugOptions {
+    slug: SlugOptions,
+    suffix: SlugSuffix,
+}
+
+impl UniqueSlugOptions {
+    /// Create options with [`slug`] and incrementing suffixes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Options used to build the slug. The maximum length includes the suffix
+    pub fn slug_options(mut self, options: SlugOptions) -> Self {
+        self.slug = options;
+        self
+    }
+
+    /// Suffix added when the slug is taken. Default: [`SlugSuffix::Increment`]
+    pub fn suffix(mut self, suffix: SlugSuffix) -> Self {
+        self.suffix = suffix;
+        self
+    }
+
+    /// Generate a slug for which `exists` returns `false`
+    ///
+    /// `exists` is called once per candidate, and must eventually return `false`. The result is
+    /// never empty: input without any usable character gets a random suffix only.
+    pub fn generate(&self, input: &str, exists: impl Fn(&str) -> bool) -> String {
+        let mut attempt = 0;
+        loop {
+            let candidate = self.candidate(input, attempt);
+            if !exists(&candidate) {
+                return candidate;
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Async version of [`generate`](Self::generate), e.g. to check the database
+    pub async fn generate_async<F, Fut>(&self, input: &str, mut exists: F) -> String
+    where
+        F: FnMut(String) -> Fut,
+        Fut: std::future::Future<Output = bool>,
+    {
+        let mut attempt = 0;
+        loop {
+            let candidate = self.candidate(input, attempt);
+            if !exists(candidate.clone()).await {
+                return candidate;
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Candidate for the given attempt, without suffix for the first attempt
+    ///
+    /// A slug without any usable character always gets a random suffix, and the suffix is
+    /// shortened if it does not fit in the maximum length.
+    fn candidate(&self, input: &str, attempt: usize) -> String {
+        let base = self.slug.apply(input);
+        if attempt == 0 && !base.is_empty() {
+            return base;
+        }
+
+        let max = self.slug.max_length.unwrap_or(usize::MAX).max(1);
+        let random_length = match self.suffix {
+            SlugSuffix::Increment => RANDOM_SUFFIX_LENGTH,
+            SlugSuffix::Random(length) => length.max(1),
+        };
+        let suffix = match self.suffix {
+            _ if base.is_empty() => random_slug_suffix(random_length.min(max)),
+            SlugSuffix::Increment if attempt < MAX_INCREMENT_ATTEMPTS => (attempt + 1).to_string(),
+            _ => random_slug_suffix(random_length),
+        };
+        // Suffix lebih panjang dari batas, ganti dengan suffix acak yang muat
+        let suffix = if suffix.len() > max {
+            random_slug_suffix(max)
+        } else {
+            suffix
+        };
+
+        let mut options = self.slug.clone();
+        if let Some(max) = self.slug.max_length {
+            let reserved = self.slug.separator.chars().count() + suffix.len();
+            options.max_length = Some(max.saturating_sub(reserved));
+        }
+        let base = options.apply(input);
+        if base.is_empty() {
+            suffix
+        } else {
+            format!("{}{}{}", base, self.slug.separator, suffix)
+        }
+    }
+}
+
+fn random_slug_suffix(length: usize) -> String {
+    // 36 karakter pertama: angka dan huruf kecil
+    nanoid_format(&ALPHANUMERIC_CHARS[..36], length)
+}
+
+/// Generate a unique slug with [`slug`] and incrementing suffixes, see [`UniqueSlugOptions`]
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::unique_slug;
+///
+/// let taken = vec!["hello-world".to_string(), "hello-world-2".to_string()];
+/// assert_eq!(unique_slug("Hello World", |s| taken.iter().any(|t| t == s)), "hello-world-3");
+/// assert_eq!(unique_slug("Hello Rust", |s| taken.iter().any(|t| t == s)), "hello-rust");
+/// ```
+pub fn unique_slug(input: &str, exists: impl Fn(&str) -> bool) -> String {
+    UniqueSlugOptions::new().generate(input, exists)
+}
+
+/// Async version of [`unique_slug`]
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::unique_slug_async;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// async fn slug_exists(slug: String) -> bool {
+///     // SELECT 1 FROM posts WHERE slug = ?
+///     slug == "hello-world"
+/// }
+///
+/// assert_eq!(unique_slug_async("Hello World", slug_exists).await, "hello-world-2");
+/// # }
+/// ```
+pub async fn unique_slug_async<F, Fut>(input: &str, exists: F) -> String
+where
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    UniqueSlugOptions::new().generate_async(input, exists).await
 }
 
 /// Checks if a given string is a valid URL.
@@ -374,6 +1016,180 @@ mod tests {
         assert_eq!(truncated_text, long_text.to_string());
     }
 
+    #[test]
+    fn test_truncate_unicode() {
+        let cases = vec![
+            ("Kopi susu", 9, "Kopi susu"),
+            ("Kopi susu", 8, "Kopi ..."),
+            ("Résumé café", 8, "Résum..."),
+            // é sebagai e + combining accent
+            ("Re\u{301}sume\u{301} cafe\u{301}", 8, "Re\u{301}sum..."),
+            ("Halo 👋🏽 dunia", 8, "Halo ..."),
+            ("Halo 👋🏽 dunia", 9, "Halo 👋🏽..."),
+            ("👨‍👩‍👧👨‍👩‍👧👨‍👩‍👧👨‍👩‍👧", 4, "👨‍👩‍👧👨‍👩‍👧👨‍👩‍👧👨‍👩‍👧"),
+            ("👨‍👩‍👧👨‍👩‍👧👨‍👩‍👧👨‍👩‍👧", 3, "..."),
+            ("Kopi susu", 2, "Ko"),
+            ("Kopi susu", 0, ""),
+            ("", 0, ""),
+        ];
+        for (input, max, expected) in cases {
+            let got = truncate(input.to_string(), max);
+            assert_eq!(
+                got, expected,
+                "truncate({:?}, {}) should be {:?}, got {:?}",
+                input, max, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncate_with() {
+        let cases = vec![
+            ("Selamat pagi dunia", 13, "…", "Selamat pagi…"),
+            ("Selamat pagi dunia", 13, "", "Selamat pagi "),
+            ("Selamat pagi dunia", 15, " (lanjut)", "Selama (lanjut)"),
+            ("Selamat", 3, "🙂🙂", "S🙂🙂"),
+            ("Selamat", 1, "🙂🙂", "S"),
+        ];
+        for (input, max, ellipsis, expected) in cases {
+            let got = truncate_with(input, max, ellipsis);
+            assert_eq!(
+                got, expected,
+                "truncate_with({:?}, {}, {:?}) should be {:?}, got {:?}",
+                input, max, ellipsis, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncate_words() {
+        let cases = vec![
+            ("lorem ipsum lorem ipsum", 18, "...", "lorem ipsum..."),
+            (
+                "lorem ipsum lorem ipsum",
+                23,
+                "...",
+                "lorem ipsum lorem ipsum",
+            ),
+            // Dipotong tepat sebelum spasi
+            ("lorem ipsum lorem ipsum", 14, "...", "lorem ipsum..."),
+            ("Halo, apa kabar?", 10, "…", "Halo, apa…"),
+            ("Halo, apa kabar?", 8, "…", "Halo…"),
+            ("Kata-kata mutiara", 12, "…", "Kata-kata…"),
+            // Apostrof dan tanda hubung di dalam kata bukan batas kata
+            ("Saya don't know", 9, "…", "Saya…"),
+            ("Ada kupu-kupu", 10, "…", "Ada…"),
+            ("I don't know", 9, "…", "I don't…"),
+            ("Pilih - lalu", 8, "…", "Pilih…"),
+            // Tidak ada batas kata, potong paksa
+            ("Supercalifragilistic", 10, "...", "Superca..."),
+            ("Hari ini 🌧️ hujan", 12, " [more]", "Hari [more]"),
+            ("Halo dunia", 3, " [more]", "Hal"),
+        ];
+        for (input, max, suffix, expected) in cases {
+            let got = truncate_words(input, max, suffix);
+            assert_eq!(
+                got, expected,
+                "truncate_words({:?}, {}, {:?}) should be {:?}, got {:?}",
+                input, max, suffix, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncate_max_bytes() {
+        let cases = vec![
+            ("Résumé café", 100, 20, "Résumé café"),
+            ("Résumé café", 100, 10, "Résum…"),
+            ("Résumé café", 100, 5, "R…"),
+            ("😀😀😀😀", 100, 10, "😀…"),
+            ("😀😀😀😀", 100, 2, ""),
+            ("abc", 100, 0, ""),
+        ];
+        for (input, max, max_bytes, expected) in cases {
+            let got = TruncateOptions::new(max)
+                .suffix("…")
+                .max_bytes(max_bytes)
+                .apply(input);
+            assert!(got.len() <= max_bytes);
+            assert_eq!(
+                got, expected,
+                "TruncateOptions::new({}).max_bytes({}).apply({:?}) should be {:?}, got {:?}",
+                max, max_bytes, input, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncate_width() {
+        let cases = vec![
+            ("Jakarta", 7, "Jakarta"),
+            ("Jakarta Selatan", 10, "Jakarta S…"),
+            ("東京タワー", 10, "東京タワー"),
+            ("東京タワー", 9, "東京タワ…"),
+            ("東京タワー", 8, "東京タ…"),
+            ("東京タワー", 2, "…"),
+            ("東京タワー", 0, ""),
+            ("Cafe\u{301} au lait", 6, "Cafe\u{301} …"),
+            ("한국어 텍스트", 7, "한국어…"),
+        ];
+        for (input, max_width, expected) in cases {
+            let got = truncate_width(input, max_width, "…");
+            assert!(display_width(&got) <= max_width);
+            assert_eq!(
+                got, expected,
+                "truncate_width({:?}, {}) should be {:?}, got {:?}",
+                input, max_width, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_pad_width() {
+        let cases = vec![
+            ("abc", 6, Align::Left, "abc   "),
+            ("abc", 6, Align::Right, "   abc"),
+            ("abc", 6, Align::Center, " abc  "),
+            ("中文", 6, Align::Left, "中文  "),
+            ("中文", 6, Align::Center, " 中文 "),
+            ("e\u{301}", 3, Align::Right, "  e\u{301}"),
+            ("too long", 3, Align::Left, "too long"),
+        ];
+        for (input, width, align, expected) in cases {
+            let got = pad_width(input, width, align);
+            assert_eq!(
+                got, expected,
+                "pad_width({:?}, {}, {:?}) should be {:?}, got {:?}",
+                input, width, align, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_wrap_width() {
+        let cases = vec![
+            ("", 10, Vec::new()),
+            ("satu dua tiga", 8, vec!["satu dua", "tiga"]),
+            ("satu  dua\n\ntiga", 20, vec!["satu dua", "", "tiga"]),
+            (
+                "Supercalifragilistic",
+                8,
+                vec!["Supercal", "ifragili", "stic"],
+            ),
+            ("a 日本語", 4, vec!["a", "日本", "語"]),
+            ("日本", 1, vec!["日", "本"]),
+            ("abc", 0, vec!["a", "b", "c"]),
+        ];
+        for (input, width, expected) in cases {
+            let got = wrap_width(input, width);
+            assert_eq!(
+                got, expected,
+                "wrap_width({:?}, {}) should be {:?}, got {:?}",
+                input, width, expected, got
+            );
+        }
+    }
+
     #[test]
     fn test_clean() {
         let html = r#"<p>Hello World</p>"#;
@@ -488,6 +1304,162 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_slug_options() {
+        let cases = vec![
+            (SlugOptions::new(), "Café Über", "café-über"),
+            (
+                SlugOptions::new().transliterate(true),
+                "Café Über straße",
+                "cafe-uber-strasse",
+            ),
+            (
+                SlugOptions::new().transliterate(true),
+                "Привет мир",
+                "privet-mir",
+            ),
+            (
+                SlugOptions::new().transliterate(true),
+                "北京欢迎你",
+                "bei-jing-huan-ying-ni",
+            ),
+            (
+                SlugOptions::new().transliterate(true),
+                "Kopi ☕ 😀 enak!",
+                "kopi-enak",
+            ),
+            (
+                SlugOptions::new().transliterate(true),
+                "naïve\u{301}",
+                "naive",
+            ),
+            (
+                SlugOptions::new().separator("_"),
+                "Hello World",
+                "hello_world",
+            ),
+            (
+                SlugOptions::new().separator(""),
+                "Hello World",
+                "helloworld",
+            ),
+            (
+                SlugOptions::new().max_length(11),
+                "Hello World Again",
+                "hello-world",
+            ),
+            (
+                SlugOptions::new().max_length(10),
+                "Hello World Again",
+                "hello",
+            ),
+            (SlugOptions::new().max_length(3), "Hello World", "hel"),
+            (SlugOptions::new().max_length(0), "Hello World", ""),
+            (
+                SlugOptions::new().stop_words(STOP_WORDS_EN),
+                "The Art of War",
+                "art-war",
+            ),
+            (
+                SlugOptions::new().stop_words(STOP_WORDS_EN),
+                "The And",
+                "the-and",
+            ),
+            (
+                SlugOptions::new()
+                    .stop_words(STOP_WORDS_ID)
+                    .stop_words(STOP_WORDS_EN),
+                "Berita dan Info di Indonesia and the World",
+                "berita-info-indonesia-world",
+            ),
+        ];
+        for (options, input, expected) in cases {
+            let got = options.apply(input);
+            assert_eq!(
+                got, expected,
+                "{:?}.apply({:?}) should be {:?}, got {:?}",
+                options, input, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_unique_slug() {
+        let taken: Vec<String> = ["post", "post-2", "post-3", "a-long-title", "a-long-2"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let exists = |s: &str| taken.iter().any(|t| t == s);
+        let cases = vec![
+            (UniqueSlugOptions::new(), "New Post", "new-post"),
+            (UniqueSlugOptions::new(), "Post", "post-4"),
+            (
+                UniqueSlugOptions::new().slug_options(SlugOptions::new().max_length(12)),
+                "A Long Title",
+                "a-long-3",
+            ),
+            (
+                UniqueSlugOptions::new().slug_options(SlugOptions::new().separator("_")),
+                "Post",
+                "post_2",
+            ),
+        ];
+        for (options, input, expected) in cases {
+            let got = options.generate(input, exists);
+            assert_eq!(
+                got, expected,
+                "{:?}.generate({:?}) should be {:?}, got {:?}",
+                options, input, expected, got
+            );
+        }
+
+        let got = UniqueSlugOptions::new()
+            .suffix(SlugSuffix::Random(5))
+            .generate("Post", exists);
+        assert!(got.starts_with("post-") && got.len() == 10, "got {:?}", got);
+        assert!(got[5..]
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+
+        // Semua angka sudah dipakai, pindah ke suffix acak
+        let got = unique_slug("Post", |s| !s.starts_with("post-") || s.len() < 13);
+        assert_eq!(got.len(), 13, "got {:?}", got);
+
+        // Tanpa karakter yang bisa dipakai, slug tetap tidak kosong
+        let is_suffix = |s: &str| {
+            s.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        };
+        let got = unique_slug("!!!", exists);
+        assert!(got.len() == 8 && is_suffix(&got), "got {:?}", got);
+        let got = UniqueSlugOptions::new()
+            .suffix(SlugSuffix::Random(4))
+            .generate("!!!", exists);
+        assert!(got.len() == 4 && is_suffix(&got), "got {:?}", got);
+
+        // Suffix harus muat dalam panjang maksimum
+        let got = UniqueSlugOptions::new()
+            .slug_options(SlugOptions::new().max_length(5))
+            .suffix(SlugSuffix::Random(8))
+            .generate("Post", exists);
+        assert!(got.len() <= 5 && is_suffix(&got), "got {:?}", got);
+        let got = UniqueSlugOptions::new()
+            .slug_options(SlugOptions::new().max_length(10))
+            .suffix(SlugSuffix::Random(3))
+            .generate("Post", exists);
+        assert!(got.starts_with("post-") && got.len() == 8, "got {:?}", got);
+        let got = UniqueSlugOptions::new()
+            .slug_options(SlugOptions::new().max_length(10))
+            .generate("Post", |s| s.len() < 10);
+        assert_eq!(got.len(), 10, "got {:?}", got);
+    }
+
+    #[tokio::test]
+    async fn test_unique_slug_async() {
+        let got = unique_slug_async("Post", |s| async move { s == "post" || s == "post-2" }).await;
+        assert_eq!(got, "post-3");
+    }
+
     #[test]
     fn test_is_url() {
         let cases = vec![("https://", false), ("https://portalnesia.com", true)];
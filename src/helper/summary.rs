@@ -0,0 +1,343 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use super::html::html_to_paragraphs;
+use super::{clean_with, markdown_to_html, SanitizePolicy};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Default reading speed, in words per minute
+pub const DEFAULT_WORDS_PER_MINUTE: usize = 200;
+
+/// Abbreviations that are usually followed by a name or number, not the end of a sentence
+///
+/// Abbreviations that often end a sentence, like `dll.` or `etc.`, are not listed.
+const ABBREVIATIONS: [&[&str]; 2] = [ENGLISH_ABBREVIATIONS, INDONESIAN_ABBREVIATIONS];
+
+const ENGLISH_ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "prof", "sr", "st", "mt", "gen", "col", "capt", "lt", "rev", "vs",
+    "e.g", "i.e", "fig", "approx", "ca",
+];
+
+const INDONESIAN_ABBREVIATIONS: &[&str] = &[
+    "bpk", "sdr", "sdri", "yth", "dr", "jl", "jln", "kab", "kec", "kel", "prov", "tgl", "hlm",
+    "drs", "dra", "ir", "hj", "kh", "a.n", "u.p", "ny", "nn",
+];
+
+/// Abbreviations that are only followed by a number, e.g. `no. 5`
+const NUMBER_ABBREVIATIONS: &[&str] = &["no", "nr", "hal", "pp", "vol"];
+
+/// Capitalized words that often start a sentence, so a single letter before them is not an initial
+const SENTENCE_STARTERS: [&[&str]; 2] = [ENGLISH_SENTENCE_STARTERS, INDONESIAN_SENTENCE_STARTERS];
+
+const ENGLISH_SENTENCE_STARTERS: &[&str] = &[
+    "a", "after", "also", "an", "and", "as", "at", "before", "but", "finally", "for", "he", "her",
+    "here", "his", "how", "however", "i", "if", "in", "it", "its", "my", "next", "no", "now", "on",
+    "or", "our", "she", "so", "that", "the", "their", "then", "there", "these", "they", "this",
+    "those", "we", "what", "when", "where", "who", "why", "yes", "you",
+];
+
+const INDONESIAN_SENTENCE_STARTERS: &[&str] = &[
+    "ada", "akhirnya", "aku", "anda", "atau", "dan", "dari", "di", "dia", "hal", "ia", "ini",
+    "itu", "jika", "juga", "kalau", "karena", "ke", "kemudian", "ketika", "kami", "kita", "lalu",
+    "maka", "mereka", "namun", "pada", "saat", "saya", "sebelum", "sekarang", "selain", "setelah",
+    "tapi", "tetapi", "untuk", "yang",
+];
+
+/// Excerpt, word count, and reading time of an article
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArticleSummary {
+    /// The first sentences of the article, as plain text
+    pub excerpt: String,
+    /// Number of words in the article
+    pub word_count: usize,
+    /// Estimated reading time in minutes, rounded up. 0 for an empty article
+    pub reading_minutes: usize,
+}
+
+/// Options to build an [`ArticleSummary`]
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::SummaryOptions;
+///
+/// let html = "<h1>Judul</h1><p>Saya bertemu dr. Budi di Jl. Sudirman. Dia sehat! Lalu pulang.</p>";
+/// let summary = SummaryOptions::new().sentences(2).summarize_html(html);
+/// assert_eq!(summary.excerpt, "Judul. Saya bertemu dr. Budi di Jl. Sudirman.");
+/// assert_eq!(summary.word_count, 12);
+/// assert_eq!(format!("{} menit baca", summary.reading_minutes), "1 menit baca");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummaryOptions {
+    sentences: usize,
+    words_per_minute: usize,
+}
+
+impl Default for SummaryOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SummaryOptions {
+    /// Create options with a 2-sentence excerpt and [`DEFAULT_WORDS_PER_MINUTE`]
+    pub fn new() -> Self {
+        SummaryOptions {
+            sentences: 2,
+            words_per_minute: DEFAULT_WORDS_PER_MINUTE,
+        }
+    }
+
+    /// Number of sentences in the excerpt. Default: 2
+    pub fn sentences(mut self, sentences: usize) -> Self {
+        self.sentences = sentences;
+        self
+    }
+
+    /// Reading speed, at least 1. Default: [`DEFAULT_WORDS_PER_MINUTE`]
+    pub fn words_per_minute(mut self, words_per_minute: usize) -> Self {
+        self.words_per_minute = words_per_minute.max(1);
+        self
+    }
+
+    /// Summarize HTML. Scripts, styles, and other markup are removed with [`clean_with`]
+    pub fn summarize_html(&self, html: &str) -> ArticleSummary {
+        let cleaned = clean_with(html, &SanitizePolicy::article());
+        self.summarize_paragraphs(&html_to_paragraphs(&cleaned))
+    }
+
+    /// Summarize Markdown, see [`markdown_to_html`]
+    pub fn summarize_markdown(&self, markdown: &str) -> ArticleSummary {
+        let html = markdown_to_html(markdown, &SanitizePolicy::article());
+        self.summarize_paragraphs(&html_to_paragraphs(&html))
+    }
+
+    fn summarize_paragraphs(&self, paragraphs: &[String]) -> ArticleSummary {
+        let mut sentences = Vec::new();
+        let mut word_count = 0;
+        for line in paragraphs.iter().flat_map(|p| p.lines()) {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            word_count += line.unicode_words().count();
+            if sentences.len() < self.sentences {
+                sentences.extend(split_sentences(line));
+            }
+        }
+
+        sentences.truncate(self.sentences);
+        ArticleSummary {
+            excerpt: sentences.join(" "),
+            word_count,
+            reading_minutes: word_count.div_ceil(self.words_per_minute),
+        }
+    }
+}
+
+/// Split text into sentences, for Indonesian and English
+///
+/// Uses Unicode sentence boundaries, but does not split after abbreviations like `dr.` or `Jl.`,
+/// after `no.` followed by a number, or after name initials like `J. K.` followed by a name.
+/// A line without final punctuation, like a heading, gets a period.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::helper::split_sentences;
+///
+/// assert_eq!(
+///     split_sentences("Mr. Smith tinggal di Jl. Merdeka no. 5. Dia ramah!"),
+///     vec!["Mr. Smith tinggal di Jl. Merdeka no. 5.", "Dia ramah!"]
+/// );
+/// ```
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let parts: Vec<&str> = text.split_sentence_bounds().collect();
+    let mut sentences: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for (index, part) in parts.iter().enumerate() {
+        current.push_str(part);
+        let next = parts.get(index + 1).copied().unwrap_or_default();
+        if next.trim().is_empty() || !continues_after(current.trim_end(), next) {
+            let sentence = current.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !sentence.is_empty() {
+                sentences.push(sentence);
+            }
+            current.clear();
+        }
+    }
+
+    if let Some(last) = sentences.last_mut() {
+        if !last
+            .ends_with(|c: char| c.is_ascii_punctuation() || matches!(c, '…' | '。' | '”' | '’'))
+        {
+            last.push('.');
+        }
+    }
+    sentences
+}
+
+/// Checks if the sentence continues after `text`, which ends with an abbreviation or an initial
+fn continues_after(text: &str, next: &str) -> bool {
+    let Some(text) = text.strip_suffix('.') else {
+        return false;
+    };
+    let word = last_token(text);
+    let lower = word.to_lowercase();
+    let next_word = next.split_whitespace().next().unwrap_or_default();
+
+    // "no. 5", tapi bukan "He said no. Then left."
+    if NUMBER_ABBREVIATIONS.contains(&lower.as_str()) {
+        return next_word.starts_with(|c: char| c.is_ascii_digit());
+    }
+    if ABBREVIATIONS
+        .iter()
+        .any(|list| list.contains(&lower.as_str()))
+    {
+        return true;
+    }
+
+    // Inisial nama, misalnya "J. K. Rowling"
+    if !is_initial(word) {
+        return false;
+    }
+    if is_initial(next_word.trim_end_matches('.')) && next_word.ends_with('.') {
+        return true;
+    }
+    let previous = last_token(text[..text.len() - word.len()].trim_end());
+    let follows_initial = previous.strip_suffix('.').is_some_and(is_initial);
+    if word == "I" && !follows_initial {
+        // Kata ganti "I" dalam bahasa Inggris
+        return false;
+    }
+
+    let next_lower = next_word
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+    next_word.starts_with(char::is_uppercase)
+        && !SENTENCE_STARTERS
+            .iter()
+            .any(|list| list.contains(&next_lower.as_str()))
+}
+
+/// Last whitespace-separated token, without an opening bracket or quote
+fn last_token(text: &str) -> &str {
+    text.rsplit(|c: char| c.is_whitespace() || c == '(' || c == '"')
+        .next()
+        .unwrap_or_default()
+}
+
+/// Single uppercase letter, e.g. `J` in `J. K. Rowling`
+fn is_initial(word: &str) -> bool {
+    let mut chars = word.chars();
+    matches!((chars.next(), chars.next()), (Some(c), None) if c.is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_sentences() {
+        let cases = vec![
+            ("", vec![]),
+            ("Judul artikel", vec!["Judul artikel."]),
+            (
+                "Saya bertemu dr. Budi di Jl. Sudirman. Dia sehat!",
+                vec!["Saya bertemu dr. Budi di Jl. Sudirman.", "Dia sehat!"],
+            ),
+            (
+                "J. K. Rowling menulis buku. Harga naik 3.5 persen.",
+                vec!["J. K. Rowling menulis buku.", "Harga naik 3.5 persen."],
+            ),
+            (
+                "Beli buah, sayur, dll. Kemudian pulang.",
+                vec!["Beli buah, sayur, dll.", "Kemudian pulang."],
+            ),
+            (
+                "He said \"Hi.\" Then left... Really?",
+                vec!["He said \"Hi.\"", "Then left...", "Really?"],
+            ),
+            ("He said no. Then left.", vec!["He said no.", "Then left."]),
+            (
+                "Jawabannya no. Lalu pergi.",
+                vec!["Jawabannya no.", "Lalu pergi."],
+            ),
+            ("Plan B. Next step.", vec!["Plan B.", "Next step."]),
+            ("Then I. Went home.", vec!["Then I.", "Went home."]),
+            (
+                "Ditulis oleh Putu A. Wijaya. Dia tinggal di Bali.",
+                vec!["Ditulis oleh Putu A. Wijaya.", "Dia tinggal di Bali."],
+            ),
+            (
+                "Prof. Andi (e.g. dosen) hadir. Yth. Bpk. Ahmad datang",
+                vec!["Prof. Andi (e.g. dosen) hadir.", "Yth. Bpk. Ahmad datang."],
+            ),
+        ];
+        for (input, expected) in cases {
+            let got = split_sentences(input);
+            assert_eq!(
+                got, expected,
+                "split_sentences({:?}) should be {:?}, got {:?}",
+                input, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let markdown =
+            "# Tips Hemat\n\nPertama, catat pengeluaran. Kedua, kurangi jajan.\n\n- Satu\n- Dua";
+        let summary = SummaryOptions::new()
+            .sentences(3)
+            .summarize_markdown(markdown);
+        assert_eq!(
+            summary,
+            ArticleSummary {
+                excerpt: "Tips Hemat. Pertama, catat pengeluaran. Kedua, kurangi jajan."
+                    .to_string(),
+                word_count: 10,
+                reading_minutes: 1,
+            }
+        );
+
+        let html = format!("<p>{}</p><script>var a = 1;</script>", "kata ".repeat(401));
+        let summary = SummaryOptions::new()
+            .words_per_minute(100)
+            .summarize_html(&html);
+        assert_eq!(summary.word_count, 401);
+        assert_eq!(summary.reading_minutes, 5);
+
+        // Angka di awal paragraf bukan penanda daftar
+        let html = "<p>2020. Tahun yang berat.</p><ol><li>Satu dua.</li></ol><blockquote>Kutipan.</blockquote><hr>";
+        let summary = SummaryOptions::new().sentences(4).summarize_html(html);
+        assert_eq!(
+            summary.excerpt,
+            "2020. Tahun yang berat. Satu dua. Kutipan."
+        );
+        assert_eq!(summary.word_count, 7);
+
+        // URL link tidak ikut dihitung
+        let html = r#"<p>Baca <a href="https://portalnesia.com/news/2024/berita-terbaru">berita</a> ini.</p>"#;
+        let summary = SummaryOptions::new().summarize_html(html);
+        assert_eq!(summary.excerpt, "Baca berita ini.");
+        assert_eq!(summary.word_count, 3);
+        let summary = SummaryOptions::new()
+            .summarize_html(r#"Baca <a href="https://x.com"><p>berita</p></a>"#);
+        assert_eq!(summary.excerpt, "Baca. berita.");
+
+        assert_eq!(
+            SummaryOptions::new().summarize_html(""),
+            ArticleSummary {
+                excerpt: String::new(),
+                word_count: 0,
+                reading_minutes: 0,
+            }
+        );
+    }
+}
@@ -8,5 +8,6 @@
 mod crypto;
 pub mod helper;
 pub mod password;
+pub mod token;
 
 pub use crypto::*;
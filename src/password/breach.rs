@@ -0,0 +1,216 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use memmap2::Mmap;
+use sha1::{Digest, Sha1};
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Length of the SHA-1 prefix used by Have I Been Pwned range files
+const PREFIX_LENGTH: usize = 5;
+
+#[derive(Debug)]
+enum Source {
+    /// Sorted `HASH:COUNT` lines, memory-mapped
+    File(Mmap),
+    /// Sorted `HASH:COUNT` or `SUFFIX:COUNT` lines in memory
+    Bytes(Vec<u8>),
+    /// Directory with one `PREFIX.txt` range file per SHA-1 prefix
+    Directory(PathBuf),
+}
+
+/// Offline breached password checker using Have I Been Pwned data
+///
+/// Supports the two layouts produced by the Pwned Passwords downloader:
+/// - a single file with sorted `SHA1:COUNT` lines, which is memory-mapped and binary-searched
+/// - a directory of range files named `<5 hex prefix>.txt` with sorted `SUFFIX:COUNT` lines
+///
+/// No request is made to an external API.
+///
+/// ## Example
+///
+/// ```no_run
+/// use pn_utils::password::BreachChecker;
+///
+/// let checker = BreachChecker::open("pwnedpasswords.txt").expect("failed to open file");
+/// let count = checker.check("password").expect("failed to read file");
+/// if count > 0 {
+///     println!("This password has been seen {} times before", count);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BreachChecker {
+    source: Source,
+}
+
+impl BreachChecker {
+    /// Open a sorted file with `SHA1:COUNT` lines
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: file hanya dibaca, dan tidak boleh diubah selama checker dipakai
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(BreachChecker {
+            source: Source::File(mmap),
+        })
+    }
+
+    /// Open a directory of range files named `<PREFIX>.txt`
+    pub fn open_dir<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not a directory", path.display()),
+            ));
+        }
+        Ok(BreachChecker {
+            source: Source::Directory(path.to_path_buf()),
+        })
+    }
+
+    /// Create checker from sorted `SHA1:COUNT` lines in memory
+    pub fn from_bytes(data: Vec<u8>) -> Self {
+        BreachChecker {
+            source: Source::Bytes(data),
+        }
+    }
+
+    /// Number of times the password appears in the breach data. `0` if not found
+    pub fn check(&self, password: &str) -> io::Result<u64> {
+        let hash = hex::encode_upper(Sha1::digest(password.as_bytes()));
+
+        match &self.source {
+            Source::File(mmap) => Ok(search(mmap, hash.as_bytes()).unwrap_or(0)),
+            Source::Bytes(data) => Ok(search(data, hash.as_bytes()).unwrap_or(0)),
+            Source::Directory(dir) => {
+                let (prefix, suffix) = hash.split_at(PREFIX_LENGTH);
+                let data = match std::fs::read(dir.join(format!("{}.txt", prefix))) {
+                    Ok(data) => data,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+                    Err(e) => return Err(e),
+                };
+                Ok(search(&data, suffix.as_bytes()).unwrap_or(0))
+            }
+        }
+    }
+
+    /// Checks if the password appears in the breach data
+    pub fn is_breached(&self, password: &str) -> io::Result<bool> {
+        Ok(self.check(password)? > 0)
+    }
+}
+
+/// Binary search sorted `KEY:COUNT` lines
+fn search(data: &[u8], key: &[u8]) -> Option<u64> {
+    let mut lo = 0;
+    let mut hi = data.len();
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        // lo selalu berada di awal baris
+        let start = data[lo..mid]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(lo, |p| lo + p + 1);
+        let end = data[start..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(data.len(), |p| start + p);
+
+        let line = data[start..end].trim_ascii();
+        let (hash, count) = match line.iter().position(|&b| b == b':') {
+            Some(p) => (&line[..p], &line[p + 1..]),
+            None => (line, &line[line.len()..]),
+        };
+
+        match compare_hex(hash, key) {
+            Ordering::Equal => {
+                let count = std::str::from_utf8(count).ok()?.trim().parse().unwrap_or(1);
+                return Some(count);
+            }
+            Ordering::Less => lo = end + 1,
+            Ordering::Greater => hi = start,
+        }
+    }
+    None
+}
+
+/// Compare hex strings case-insensitively
+fn compare_hex(a: &[u8], b: &[u8]) -> Ordering {
+    a.iter()
+        .map(u8::to_ascii_uppercase)
+        .cmp(b.iter().map(u8::to_ascii_uppercase))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SHA-1 dari "password" dan "123456"
+    const PASSWORD: &str = "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8";
+    const NUMBERS: &str = "7C4A8D09CA3762AF61E59520943DC26494F8941B";
+
+    fn sorted_lines() -> String {
+        let mut lines = [
+            format!("{}:3861493", PASSWORD),
+            format!("{}:37359195", NUMBERS),
+            "0000000A0E3B9F25FF41DE4B5AC238C2D545C7A8:15".to_string(),
+            "FFFFFFFEE791CBAC0F6305CAF0CEE06BBE131160:2".to_string(),
+        ];
+        lines.sort();
+        lines.join("\r\n")
+    }
+
+    #[test]
+    fn test_search() {
+        let data = sorted_lines();
+        assert_eq!(search(data.as_bytes(), PASSWORD.as_bytes()), Some(3861493));
+        assert_eq!(search(data.as_bytes(), NUMBERS.as_bytes()), Some(37359195));
+        assert_eq!(
+            search(data.as_bytes(), b"0000000a0e3b9f25ff41de4b5ac238c2d545c7a8"),
+            Some(15)
+        );
+        assert_eq!(
+            search(data.as_bytes(), b"5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD9"),
+            None
+        );
+        assert_eq!(search(b"", PASSWORD.as_bytes()), None);
+    }
+
+    #[test]
+    fn test_check_file() {
+        let path = std::env::temp_dir().join(format!("pn_utils_breach_{}.txt", std::process::id()));
+        std::fs::write(&path, sorted_lines()).expect("failed to write file");
+
+        let checker = BreachChecker::open(&path).expect("failed to open file");
+        assert_eq!(checker.check("password").unwrap(), 3861493);
+        assert!(checker.is_breached("123456").unwrap());
+        assert!(!checker.is_breached("kucing-hitam-makan-durian").unwrap());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_check_dir() {
+        let dir = std::env::temp_dir().join(format!("pn_utils_breach_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("failed to create dir");
+        std::fs::write(
+            dir.join("5BAA6.txt"),
+            "003D68EB55068C33ACE09247EE4C639306B:3\r\n1E4C9B93F3F0682250B6CF8331B7EE68FD8:3861493\r\n",
+        )
+        .expect("failed to write file");
+
+        let checker = BreachChecker::open_dir(&dir).expect("failed to open dir");
+        assert_eq!(checker.check("password").unwrap(), 3861493);
+        assert_eq!(checker.check("123456").unwrap(), 0);
+
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(BreachChecker::open_dir(&dir).is_err());
+    }
+}
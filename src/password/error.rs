@@ -0,0 +1,71 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use bcrypt::BcryptError;
+use std::error::Error;
+use std::fmt;
+
+/// Error returned by the password module
+#[derive(Debug)]
+pub enum PasswordError {
+    /// Error from bcrypt
+    Bcrypt(BcryptError),
+    /// Error from Argon2 or the PHC string parser
+    Argon2(argon2::password_hash::Error),
+    /// Failed to generate random salt
+    Random(String),
+    /// Invalid options or parameters, e.g. generator options or KDF parameters
+    InvalidOptions(String),
+    /// Background hashing task panicked or was cancelled
+    Task(String),
+    /// Stored hash is corrupted or not in the expected format
+    MalformedHash(String),
+}
+
+impl fmt::Display for PasswordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordError::Bcrypt(e) => write!(f, "bcrypt error: {}", e),
+            PasswordError::Argon2(e) => write!(f, "argon2 error: {}", e),
+            PasswordError::Random(e) => write!(f, "random error: {}", e),
+            PasswordError::InvalidOptions(e) => write!(f, "invalid options: {}", e),
+            PasswordError::Task(e) => write!(f, "task error: {}", e),
+            PasswordError::MalformedHash(e) => write!(f, "malformed hash: {}", e),
+        }
+    }
+}
+
+impl Error for PasswordError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PasswordError::Bcrypt(e) => Some(e),
+            PasswordError::Argon2(e) => Some(e),
+            PasswordError::Random(_)
+            | PasswordError::InvalidOptions(_)
+            | PasswordError::Task(_)
+            | PasswordError::MalformedHash(_) => None,
+        }
+    }
+}
+
+impl From<BcryptError> for PasswordError {
+    fn from(e: BcryptError) -> Self {
+        PasswordError::Bcrypt(e)
+    }
+}
+
+impl From<argon2::password_hash::Error> for PasswordError {
+    fn from(e: argon2::password_hash::Error) -> Self {
+        PasswordError::Argon2(e)
+    }
+}
+
+impl From<argon2::Error> for PasswordError {
+    fn from(e: argon2::Error) -> Self {
+        PasswordError::Argon2(e.into())
+    }
+}
@@ -0,0 +1,369 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use super::{Language, PasswordError};
+use lazy_static::lazy_static;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::Rng;
+
+/// Characters that are easy to confuse, excluded from generated passwords by default
+pub const LOOK_ALIKE_CHARS: [char; 8] = ['0', 'O', 'o', '1', 'l', 'I', 'i', '|'];
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!@#$%^&*()-_=+[]{};:,.?/|~";
+
+lazy_static! {
+    static ref WORDLIST_EN: Vec<&'static str> = wordlist(include_str!("data/passphrase_en.txt"));
+    static ref WORDLIST_ID: Vec<&'static str> = wordlist(include_str!("data/passphrase_id.txt"));
+}
+
+/// Generated password or passphrase
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedPassword {
+    /// Generated password or passphrase
+    pub password: String,
+    /// Estimated entropy in bits
+    pub entropy: f64,
+}
+
+/// Random password generator
+///
+/// Every enabled character class is guaranteed to appear at least once.
+/// Default: 16 characters with lowercase, uppercase and digits, without look-alike characters.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::PasswordGenerator;
+///
+/// let generated = PasswordGenerator::new()
+///     .length(20)
+///     .symbols(true)
+///     .generate()
+///     .expect("failed to generate password");
+/// println!("{} ({:.0} bits)", generated.password, generated.entropy);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordGenerator {
+    length: usize,
+    lowercase: bool,
+    uppercase: bool,
+    digits: bool,
+    symbols: bool,
+    exclude_look_alike: bool,
+}
+
+impl Default for PasswordGenerator {
+    fn default() -> Self {
+        PasswordGenerator {
+            length: 16,
+            lowercase: true,
+            uppercase: true,
+            digits: true,
+            symbols: false,
+            exclude_look_alike: true,
+        }
+    }
+}
+
+impl PasswordGenerator {
+    /// Create generator with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of characters
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Require lowercase letters
+    pub fn lowercase(mut self, enabled: bool) -> Self {
+        self.lowercase = enabled;
+        self
+    }
+
+    /// Require uppercase letters
+    pub fn uppercase(mut self, enabled: bool) -> Self {
+        self.uppercase = enabled;
+        self
+    }
+
+    /// Require digits
+    pub fn digits(mut self, enabled: bool) -> Self {
+        self.digits = enabled;
+        self
+    }
+
+    /// Require symbols
+    pub fn symbols(mut self, enabled: bool) -> Self {
+        self.symbols = enabled;
+        self
+    }
+
+    /// Exclude [`LOOK_ALIKE_CHARS`]
+    pub fn exclude_look_alike(mut self, exclude: bool) -> Self {
+        self.exclude_look_alike = exclude;
+        self
+    }
+
+    /// Generate random password
+    pub fn generate(&self) -> Result<GeneratedPassword, PasswordError> {
+        let classes: Vec<Vec<char>> = [
+            (self.lowercase, LOWERCASE),
+            (self.uppercase, UPPERCASE),
+            (self.digits, DIGITS),
+            (self.symbols, SYMBOLS),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, chars)| {
+            chars
+                .chars()
+                .filter(|c| !self.exclude_look_alike || !LOOK_ALIKE_CHARS.contains(c))
+                .collect()
+        })
+        .collect();
+
+        if classes.is_empty() {
+            return Err(PasswordError::InvalidOptions(
+                "at least one character class must be enabled".to_string(),
+            ));
+        }
+        if self.length < classes.len() {
+            return Err(PasswordError::InvalidOptions(format!(
+                "length must be at least {} to include every character class",
+                classes.len()
+            )));
+        }
+
+        let pool: Vec<char> = classes.iter().flatten().copied().collect();
+        let mut rng = rand::rng();
+
+        // Satu karakter dari setiap kelas, sisanya dari semua kelas
+        let mut password: Vec<char> = classes
+            .iter()
+            .filter_map(|class| class.choose(&mut rng).copied())
+            .collect();
+        while password.len() < self.length {
+            password.push(pool[rng.random_range(0..pool.len())]);
+        }
+        password.shuffle(&mut rng);
+
+        Ok(GeneratedPassword {
+            password: password.into_iter().collect(),
+            entropy: self.length as f64 * (pool.len() as f64).log2(),
+        })
+    }
+}
+
+/// Diceware-style passphrase generator
+///
+/// Words are picked from an embedded wordlist: the BIP-39 English list (2048 words), or a list of
+/// common Indonesian words. Default: 5 English words separated by `-`.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{Language, PassphraseGenerator};
+///
+/// let generated = PassphraseGenerator::new()
+///     .language(Language::Indonesian)
+///     .words(6)
+///     .generate()
+///     .expect("failed to generate passphrase");
+/// println!("{} ({:.0} bits)", generated.password, generated.entropy);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassphraseGenerator {
+    words: usize,
+    language: Language,
+    separator: String,
+    capitalize: bool,
+    append_digit: bool,
+}
+
+impl Default for PassphraseGenerator {
+    fn default() -> Self {
+        PassphraseGenerator {
+            words: 5,
+            language: Language::English,
+            separator: "-".to_string(),
+            capitalize: false,
+            append_digit: false,
+        }
+    }
+}
+
+impl PassphraseGenerator {
+    /// Create generator with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of words
+    pub fn words(mut self, words: usize) -> Self {
+        self.words = words;
+        self
+    }
+
+    /// Wordlist language
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Separator between words
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Capitalize the first letter of each word
+    pub fn capitalize(mut self, capitalize: bool) -> Self {
+        self.capitalize = capitalize;
+        self
+    }
+
+    /// Append a random digit to a random word
+    pub fn append_digit(mut self, append: bool) -> Self {
+        self.append_digit = append;
+        self
+    }
+
+    /// Generate random passphrase
+    pub fn generate(&self) -> Result<GeneratedPassword, PasswordError> {
+        if self.words == 0 {
+            return Err(PasswordError::InvalidOptions(
+                "words must be at least 1".to_string(),
+            ));
+        }
+
+        let list: &[&str] = match self.language {
+            Language::English => &WORDLIST_EN,
+            Language::Indonesian => &WORDLIST_ID,
+        };
+        let mut rng = rand::rng();
+
+        let mut words: Vec<String> = (0..self.words)
+            .map(|_| {
+                let word = list[rng.random_range(0..list.len())];
+                if self.capitalize {
+                    crate::helper::capitalize_first(word.to_string())
+                } else {
+                    word.to_string()
+                }
+            })
+            .collect();
+
+        let mut entropy = self.words as f64 * (list.len() as f64).log2();
+        if self.append_digit {
+            let index = rng.random_range(0..words.len());
+            words[index].push(char::from(b'0' + rng.random_range(0..10u8)));
+            entropy += 10f64.log2() + (self.words as f64).log2();
+        }
+
+        Ok(GeneratedPassword {
+            password: words.join(&self.separator),
+            entropy,
+        })
+    }
+}
+
+fn wordlist(list: &'static str) -> Vec<&'static str> {
+    list.lines()
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_password() {
+        for _ in 0..50 {
+            let generated = PasswordGenerator::new()
+                .length(8)
+                .symbols(true)
+                .generate()
+                .expect("failed to generate password");
+            let password = generated.password;
+
+            assert_eq!(password.chars().count(), 8);
+            assert!(password.chars().any(|c| c.is_ascii_lowercase()));
+            assert!(password.chars().any(|c| c.is_ascii_uppercase()));
+            assert!(password.chars().any(|c| c.is_ascii_digit()));
+            assert!(password.chars().any(|c| SYMBOLS.contains(c)));
+            assert!(
+                !password.chars().any(|c| LOOK_ALIKE_CHARS.contains(&c)),
+                "{:?} contains look-alike characters",
+                password
+            );
+        }
+
+        let generated = PasswordGenerator::new()
+            .digits(false)
+            .uppercase(false)
+            .exclude_look_alike(false)
+            .generate()
+            .expect("failed to generate password");
+        assert!((generated.entropy - 16.0 * 26f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_generate_password_invalid_options() {
+        assert!(PasswordGenerator::new().length(2).generate().is_err());
+        assert!(PasswordGenerator::new()
+            .lowercase(false)
+            .uppercase(false)
+            .digits(false)
+            .generate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_generate_passphrase() {
+        assert_eq!(WORDLIST_EN.len(), 2048);
+
+        let generated = PassphraseGenerator::new()
+            .generate()
+            .expect("failed to generate passphrase");
+        let words: Vec<&str> = generated.password.split('-').collect();
+        assert_eq!(words.len(), 5);
+        assert!(words.iter().all(|w| WORDLIST_EN.contains(w)));
+        assert!((generated.entropy - 55.0).abs() < 1e-9);
+
+        let generated = PassphraseGenerator::new()
+            .language(Language::Indonesian)
+            .words(4)
+            .separator(" ")
+            .capitalize(true)
+            .append_digit(true)
+            .generate()
+            .expect("failed to generate passphrase");
+        let words: Vec<&str> = generated.password.split(' ').collect();
+        assert_eq!(words.len(), 4);
+        assert!(words
+            .iter()
+            .all(|w| w.starts_with(|c: char| c.is_uppercase())));
+        assert_eq!(
+            generated
+                .password
+                .chars()
+                .filter(|c| c.is_ascii_digit())
+                .count(),
+            1
+        );
+
+        assert!(PassphraseGenerator::new().words(0).generate().is_err());
+    }
+}
@@ -0,0 +1,530 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use super::{
+    verify_pbkdf2, verify_scrypt, PasswordError, BCRYPT_MAX_PASSWORD_BYTES, MAX_OUTPUT_LENGTH,
+    PBKDF2_PREFIX,
+};
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier as _, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use bcrypt::{hash, verify, BcryptError, DEFAULT_COST};
+use rand::rngs::OsRng;
+use rand::TryRngCore;
+use sha2::{Digest, Sha256};
+use unicode_normalization::UnicodeNormalization;
+
+/// Marker of bcrypt hashes whose password was pre-hashed with SHA-256, e.g. `$sha256$2b$12$...`
+const PREHASH_MARKER: &str = "$sha256";
+
+/// Highest Argon2 memory size accepted for new and stored hashes, in KiB (1 GiB)
+pub(crate) const ARGON2_MAX_MEMORY_COST: u32 = 1 << 20;
+
+/// Highest Argon2 iterations accepted for new and stored hashes
+pub(crate) const ARGON2_MAX_ITERATIONS: u32 = 100;
+
+/// Highest Argon2 parallelism accepted for new and stored hashes
+const ARGON2_MAX_PARALLELISM: u32 = 16;
+
+/// Password hashing algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    /// bcrypt (`$2a$`, `$2b$`, `$2x$`, `$2y$`), optionally pre-hashed (`$sha256$2b$`)
+    Bcrypt,
+    /// Argon2 in PHC string format (`$argon2id$`, `$argon2i$`, `$argon2d$`)
+    Argon2,
+    /// scrypt in PHC string format (`$scrypt$`)
+    Scrypt,
+    /// PBKDF2-SHA256 in Django format (`pbkdf2_sha256$`)
+    Pbkdf2,
+}
+
+impl HashAlgorithm {
+    /// Detect the algorithm from the prefix of a hashed password
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use pn_utils::password::HashAlgorithm;
+    ///
+    /// let algorithm = HashAlgorithm::detect("$argon2id$v=19$m=19456,t=2,p=1$c2FsdA$aGFzaA");
+    /// assert_eq!(algorithm, Some(HashAlgorithm::Argon2));
+    /// ```
+    pub fn detect(hashed_password: &str) -> Option<Self> {
+        const BCRYPT_PREFIXES: [&str; 4] = ["$2a$", "$2b$", "$2x$", "$2y$"];
+        const ARGON2_PREFIXES: [&str; 3] = ["$argon2id$", "$argon2i$", "$argon2d$"];
+
+        let bcrypt_hash = strip_prehash(hashed_password).unwrap_or(hashed_password);
+        if BCRYPT_PREFIXES.iter().any(|p| bcrypt_hash.starts_with(p)) {
+            Some(HashAlgorithm::Bcrypt)
+        } else if ARGON2_PREFIXES
+            .iter()
+            .any(|p| hashed_password.starts_with(p))
+        {
+            Some(HashAlgorithm::Argon2)
+        } else if hashed_password.starts_with("$scrypt$") {
+            Some(HashAlgorithm::Scrypt)
+        } else if hashed_password.starts_with(PBKDF2_PREFIX) {
+            Some(HashAlgorithm::Pbkdf2)
+        } else {
+            None
+        }
+    }
+}
+
+/// Argon2id cost parameters
+///
+/// Default values follow the OWASP recommendation (19 MiB memory, 2 iterations, 1 lane).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+    /// Memory size in KiB
+    pub memory_cost: u32,
+    /// Number of iterations
+    pub iterations: u32,
+    /// Degree of parallelism
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    fn default() -> Self {
+        Argon2Params {
+            memory_cost: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+/// Hashes the password using bcrypt
+///
+/// Always uses [`bcrypt::DEFAULT_COST`]. Use [`crate::password::PasswordHasher`] to configure the cost.
+///
+/// ## Example
+///
+/// ```
+/// let hashed = pn_utils::password::hash_password("this is secret password".to_string());
+/// println!("{}",hashed.unwrap_or("failed".to_string()));
+/// ```
+pub fn hash_password(password: String) -> Result<String, BcryptError> {
+    hash(password, DEFAULT_COST)
+}
+
+/// Hashes the password using bcrypt, pre-hashing passwords longer than 72 bytes
+///
+/// bcrypt silently ignores everything after [`BCRYPT_MAX_PASSWORD_BYTES`]. Longer passwords are
+/// replaced with `base64(SHA-256(password))`, and the hash is marked with a `$sha256$` prefix so
+/// [`compare_password`] does the same. Shorter passwords produce a normal bcrypt hash.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{compare_password, hash_password_prehashed};
+///
+/// let password = "kata sandi yang sangat panjang ".repeat(3);
+/// let hashed = hash_password_prehashed(password.clone()).unwrap();
+/// assert!(hashed.starts_with("$sha256$2b$"));
+///
+/// // Karakter setelah byte ke-72 tetap diperhitungkan
+/// assert!(!compare_password(format!("{}!", password.trim_end()), hashed.clone()));
+/// assert!(compare_password(password, hashed));
+/// ```
+pub fn hash_password_prehashed(password: String) -> Result<String, BcryptError> {
+    hash_bcrypt_prehashed(password, DEFAULT_COST)
+}
+
+/// bcrypt with the given cost, pre-hashing passwords longer than 72 bytes
+pub(crate) fn hash_bcrypt_prehashed(password: String, cost: u32) -> Result<String, BcryptError> {
+    if password.len() <= BCRYPT_MAX_PASSWORD_BYTES {
+        return hash(password, cost);
+    }
+    let hashed = hash(prehash(&password), cost)?;
+    Ok(format!("{}{}", PREHASH_MARKER, hashed))
+}
+
+/// Normalize the password to Unicode NFKC
+///
+/// The same password typed on different devices can use different code points, e.g. `é` as one
+/// character or as `e` followed by a combining accent. NFKC makes them equal.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::normalize_password;
+///
+/// assert_eq!(normalize_password("cafe\u{301}"), normalize_password("caf\u{e9}"));
+/// assert_eq!(normalize_password("ｐａｓｓ"), "pass");
+/// ```
+pub fn normalize_password(password: &str) -> String {
+    password.nfkc().collect()
+}
+
+/// Hashes the password using Argon2id
+///
+/// Returns a PHC string, e.g. `$argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>`
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{hash_password_argon2, Argon2Params};
+///
+/// let hashed = hash_password_argon2("this is secret password".to_string(), Argon2Params::default());
+/// println!("{}",hashed.unwrap_or("failed".to_string()));
+/// ```
+pub fn hash_password_argon2(
+    password: String,
+    params: Argon2Params,
+) -> Result<String, PasswordError> {
+    check_argon2_params(params)?;
+    let params = Params::new(
+        params.memory_cost,
+        params.iterations,
+        params.parallelism,
+        None,
+    )?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+    let salt = random_salt()?;
+    let hashed = argon2.hash_password(password.as_bytes(), &salt)?;
+    Ok(hashed.to_string())
+}
+
+/// Compares a password with a hashed password
+///
+/// The algorithm is detected from the hash prefix, so bcrypt, Argon2, scrypt and PBKDF2 (Django)
+/// hashes are supported.
+///
+/// ## Example
+///
+/// ```
+/// let password = "this is secret password";
+/// let hashed_password = "$2b$12$jfIoU3eWvkujdHnwpDf01ek0zKHNxOxVn7ifbEMV4eIPE.j7ZWBR."; // This is only examples
+/// let is_password_valid = pn_utils::password::compare_password(password.to_string(),hashed_password.to_string());
+/// println!("is password valid? {}",is_password_valid);
+/// ```
+///
+/// Malformed hashes are treated as a wrong password. Use [`verify_password`] to tell them apart.
+pub fn compare_password(password: String, hashed_password: String) -> bool {
+    verify_password(password, hashed_password).unwrap_or(false)
+}
+
+/// Compares a password with a hashed password, returning an error for malformed hashes
+///
+/// `Ok(false)` means the password is wrong, while `Err` means the stored hash is corrupted or
+/// in an unknown format.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::verify_password;
+///
+/// let hashed_password = bcrypt::hash("this is secret password", 4).unwrap();
+/// assert_eq!(verify_password("wrong password".to_string(), hashed_password).ok(), Some(false));
+///
+/// let corrupted = "$2b$04$corrupted".to_string();
+/// assert!(verify_password("this is secret password".to_string(), corrupted).is_err());
+/// ```
+pub fn verify_password(password: String, hashed_password: String) -> Result<bool, PasswordError> {
+    if let Some(bcrypt_hash) = strip_prehash(&hashed_password) {
+        return Ok(verify(prehash(&password), bcrypt_hash)?);
+    }
+
+    match HashAlgorithm::detect(&hashed_password) {
+        Some(HashAlgorithm::Argon2) => verify_argon2(&password, &hashed_password),
+        Some(HashAlgorithm::Scrypt) => verify_scrypt(&password, &hashed_password),
+        Some(HashAlgorithm::Pbkdf2) => verify_pbkdf2(&password, &hashed_password),
+        _ => Ok(verify(password, hashed_password.as_str())?),
+    }
+}
+
+/// Spend the same time as verifying a password, without a stored hash
+///
+/// Call this when the username is not found, so the response time does not reveal which
+/// accounts exist. Always returns `false`. The cost matches [`hash_password`]; use
+/// [`crate::password::PasswordHasher::dummy_verify`] for other costs.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{compare_password, dummy_verify};
+///
+/// fn login(password: String, stored_hash: Option<String>) -> bool {
+///     match stored_hash {
+///         Some(hashed_password) => compare_password(password, hashed_password),
+///         None => dummy_verify(password),
+///     }
+/// }
+/// ```
+pub fn dummy_verify(password: String) -> bool {
+    // Verifikasi bcrypt sama dengan menghitung hash dengan cost yang sama
+    let _ = hash(password, DEFAULT_COST);
+    false
+}
+
+/// Random 16-byte salt for PHC strings
+pub(crate) fn random_salt() -> Result<SaltString, PasswordError> {
+    // Generate salt secara acak
+    let mut salt = [0u8; 16];
+    let mut rng = OsRng;
+    if let Err(e) = rng.try_fill_bytes(&mut salt) {
+        return Err(PasswordError::Random(e.to_string()));
+    }
+    Ok(SaltString::encode_b64(&salt)?)
+}
+
+/// Remove the pre-hash marker, `$sha256$2b$...` => `$2b$...`
+pub(crate) fn strip_prehash(hashed_password: &str) -> Option<&str> {
+    hashed_password
+        .strip_prefix(PREHASH_MARKER)
+        .filter(|rest| rest.starts_with('$'))
+}
+
+/// `base64(SHA-256(password))`, always 44 bytes
+fn prehash(password: &str) -> String {
+    STANDARD.encode(Sha256::digest(password.as_bytes()))
+}
+
+/// Reject Argon2 parameters outside the limits, so every new hash can be verified again
+pub(crate) fn check_argon2_params(params: Argon2Params) -> Result<(), PasswordError> {
+    if params.memory_cost > ARGON2_MAX_MEMORY_COST
+        || params.iterations > ARGON2_MAX_ITERATIONS
+        || params.parallelism > ARGON2_MAX_PARALLELISM
+    {
+        return Err(PasswordError::InvalidOptions(format!(
+            "argon2: memory cost must be at most {} KiB, iterations at most {}, and parallelism at most {}",
+            ARGON2_MAX_MEMORY_COST, ARGON2_MAX_ITERATIONS, ARGON2_MAX_PARALLELISM
+        )));
+    }
+    Ok(())
+}
+
+fn verify_argon2(password: &str, hashed_password: &str) -> Result<bool, PasswordError> {
+    use argon2::password_hash::Error;
+
+    let parsed = PasswordHash::new(hashed_password)?;
+    // PHC string tanpa hash tidak akan pernah cocok
+    let Some(output) = parsed.hash else {
+        return Err(Error::PhcStringField.into());
+    };
+
+    // Parameter dari hash yang rusak bisa membuat verifikasi berjalan sangat lama
+    let params = Params::try_from(&parsed)?;
+    check_argon2_params(Argon2Params {
+        memory_cost: params.m_cost(),
+        iterations: params.t_cost(),
+        parallelism: params.p_cost(),
+    })?;
+    if output.len() > MAX_OUTPUT_LENGTH {
+        return Err(PasswordError::InvalidOptions(
+            "argon2: hash of the stored hash is too long".to_string(),
+        ));
+    }
+    match Argon2::default().verify_password(password.as_bytes(), &parsed) {
+        Ok(()) => Ok(true),
+        Err(Error::Password) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_and_compare_password() {
+        let password = "my_secret_password";
+        let hashed_password =
+            hash_password(password.to_string()).expect("failed to hashed password");
+
+        // Ensure the password matches the hash
+        assert!(compare_password(
+            password.to_string(),
+            hashed_password.clone()
+        ));
+
+        // Ensure a different password does not match
+        assert!(!compare_password(
+            "wrong_password".to_string(),
+            hashed_password.clone()
+        ));
+    }
+
+    #[test]
+    fn test_verify_password() {
+        let password = "my_secret_password";
+        let hashed_password = hash("my_secret_password", 4).expect("failed to hashed password");
+        let argon = hash_password_argon2(
+            password.to_string(),
+            Argon2Params {
+                memory_cost: 1024,
+                iterations: 1,
+                parallelism: 1,
+            },
+        )
+        .expect("failed to hashed password");
+
+        let cases = vec![
+            (password, hashed_password.as_str(), Some(true)),
+            ("wrong_password", hashed_password.as_str(), Some(false)),
+            (password, argon.as_str(), Some(true)),
+            ("wrong_password", argon.as_str(), Some(false)),
+            // Hash rusak
+            (password, "$2b$04$corrupted", None),
+            (password, "$argon2id$v=19$m=1024", None),
+            (password, "5f4dcc3b5aa765d61d8327deb882cf99", None),
+            (password, "", None),
+            (
+                "password",
+                "pbkdf2_sha256$1000$seasalt1234$iX6D1OoXKWsmC82FxfLWOA73XMoClH4DFhojI/+rn44=",
+                Some(true),
+            ),
+            (password, "pbkdf2_sha256$1000$seasalt1234", None),
+            (password, "$scrypt$ln=10,r=8,p=1", None),
+        ];
+        for (input, hashed, expected) in cases {
+            let got = verify_password(input.to_string(), hashed.to_string()).ok();
+            assert_eq!(
+                got, expected,
+                "verify_password({:?}, {:?}) should be {:?}, got {:?}",
+                input, hashed, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash_password_prehashed() {
+        // 73 byte, bcrypt biasa mengabaikan byte terakhir
+        let password = format!("{}a", "x".repeat(72));
+        let other = format!("{}b", "x".repeat(72));
+        let hashed = hash_bcrypt_prehashed(password.clone(), 4).expect("failed to hashed password");
+
+        assert!(hashed.starts_with("$sha256$2b$04$"));
+        assert_eq!(HashAlgorithm::detect(&hashed), Some(HashAlgorithm::Bcrypt));
+        assert!(compare_password(password.clone(), hashed.clone()));
+        assert!(!compare_password(other.clone(), hashed));
+
+        let plain = hash(password.clone(), 4).expect("failed to hashed password");
+        assert!(compare_password(other, plain));
+
+        let short =
+            hash_bcrypt_prehashed("password".to_string(), 4).expect("failed to hashed password");
+        assert!(short.starts_with("$2b$04$"));
+    }
+
+    #[test]
+    fn test_normalize_password() {
+        let cases = vec![
+            ("password", "password"),
+            ("cafe\u{301}", "caf\u{e9}"),
+            ("ｐａｓｓ１２３", "pass123"),
+            ("\u{fb01}le", "file"),
+        ];
+        for (input, expected) in cases {
+            let got = normalize_password(input);
+            assert_eq!(
+                got, expected,
+                "normalize_password({:?}) should be {:?}, got {:?}",
+                input, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash_and_compare_password_argon2() {
+        let password = "my_secret_password";
+        let params = Argon2Params {
+            memory_cost: 1024,
+            iterations: 1,
+            parallelism: 1,
+        };
+        let hashed_password =
+            hash_password_argon2(password.to_string(), params).expect("failed to hashed password");
+
+        assert!(hashed_password.starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+        assert_eq!(
+            HashAlgorithm::detect(&hashed_password),
+            Some(HashAlgorithm::Argon2)
+        );
+
+        assert!(compare_password(
+            password.to_string(),
+            hashed_password.clone()
+        ));
+        assert!(!compare_password(
+            "wrong_password".to_string(),
+            hashed_password.clone()
+        ));
+
+        // Parameter terlalu besar ditolak, baik saat membuat maupun memverifikasi hash
+        let got = hash_password_argon2(
+            password.to_string(),
+            Argon2Params {
+                iterations: ARGON2_MAX_ITERATIONS + 1,
+                ..params
+            },
+        );
+        assert!(
+            matches!(got, Err(PasswordError::InvalidOptions(_))),
+            "got {:?}",
+            got
+        );
+        let cases = vec![
+            "$argon2id$v=19$m=4194304,t=1,p=1$c2FsdHNhbHQ$aGFzaGhhc2hoYXNoaGFzaA",
+            "$argon2id$v=19$m=1024,t=100000,p=1$c2FsdHNhbHQ$aGFzaGhhc2hoYXNoaGFzaA",
+            "$argon2id$v=19$m=1024,t=1,p=64$c2FsdHNhbHQ$aGFzaGhhc2hoYXNoaGFzaA",
+        ];
+        for input in cases {
+            let got = verify_password(password.to_string(), input.to_string());
+            assert!(
+                matches!(got, Err(PasswordError::InvalidOptions(_))),
+                "verify_password({:?}) should be invalid options, got {:?}",
+                input,
+                got
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_algorithm() {
+        let cases = vec![
+            (
+                "$2b$12$jfIoU3eWvkujdHnwpDf01ek0zKHNxOxVn7ifbEMV4eIPE.j7ZWBR.",
+                Some(HashAlgorithm::Bcrypt),
+            ),
+            ("$2y$10$abcdefghijklmnopqrstuv", Some(HashAlgorithm::Bcrypt)),
+            (
+                "$argon2id$v=19$m=19456,t=2,p=1$c2FsdA$aGFzaA",
+                Some(HashAlgorithm::Argon2),
+            ),
+            (
+                "$argon2i$v=19$m=4096,t=3,p=1$c2FsdA$aGFzaA",
+                Some(HashAlgorithm::Argon2),
+            ),
+            ("5f4dcc3b5aa765d61d8327deb882cf99", None),
+            ("", None),
+        ];
+
+        for (input, expected) in cases {
+            let got = HashAlgorithm::detect(input);
+            assert_eq!(
+                got, expected,
+                "HashAlgorithm::detect({:?}) should be {:?}, got {:?}",
+                input, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_argon2_params() {
+        let params = Argon2Params {
+            memory_cost: 1,
+            iterations: 0,
+            parallelism: 1,
+        };
+        assert!(hash_password_argon2("password".to_string(), params).is_err());
+    }
+}
@@ -0,0 +1,805 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use super::{
+    check_argon2_params, check_pbkdf2_iterations, check_scrypt_params, hash_bcrypt_prehashed,
+    hash_password_argon2, hash_password_pbkdf2, hash_password_scrypt, normalize_password,
+    pbkdf2_iterations, scrypt_params, split_peppered, strip_prehash, verify_password,
+    verify_password_peppered, Argon2Params, HashAlgorithm, PasswordError, Pepper, ScryptParams,
+    ARGON2_MAX_ITERATIONS, PBKDF2_DEFAULT_ITERATIONS, PBKDF2_MAX_ITERATIONS, SCRYPT_MAX_LOG_N,
+};
+use bcrypt::{BcryptError, DEFAULT_COST};
+use std::time::{Duration, Instant};
+
+/// Minimum cost accepted by bcrypt
+pub const BCRYPT_MIN_COST: u32 = 4;
+/// Maximum cost accepted by bcrypt
+pub const BCRYPT_MAX_COST: u32 = 31;
+
+/// Result of [`PasswordHasher::verify_and_upgrade`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordVerification {
+    /// Whether the password matches the hash
+    pub valid: bool,
+    /// New hash to persist when the stored hash is outdated
+    pub new_hash: Option<String>,
+}
+
+/// Password hashing policy
+///
+/// Holds the algorithm and cost that new hashes should use, and detects stored hashes that
+/// were made with an older algorithm or cost so they can be upgraded on the next login.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::PasswordHasher;
+///
+/// let hasher = PasswordHasher::bcrypt(4);
+/// let stored = bcrypt::hash("secret password", 5).unwrap();
+///
+/// let result = hasher
+///     .verify_and_upgrade("secret password".to_string(), stored)
+///     .expect("failed to verify password");
+/// assert!(result.valid);
+///
+/// if let Some(new_hash) = result.new_hash {
+///     // save new_hash to database
+///     assert!(new_hash.starts_with("$2b$04$"));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordHasher {
+    algorithm: HashAlgorithm,
+    bcrypt_cost: u32,
+    argon2: Argon2Params,
+    scrypt: ScryptParams,
+    pbkdf2_iterations: u32,
+    pepper: Option<Pepper>,
+    prehash: bool,
+    normalize: bool,
+}
+
+impl Default for PasswordHasher {
+    fn default() -> Self {
+        PasswordHasher {
+            algorithm: HashAlgorithm::Bcrypt,
+            bcrypt_cost: DEFAULT_COST,
+            argon2: Argon2Params::default(),
+            scrypt: ScryptParams::default(),
+            pbkdf2_iterations: PBKDF2_DEFAULT_ITERATIONS,
+            pepper: None,
+            prehash: false,
+            normalize: false,
+        }
+    }
+}
+
+impl PasswordHasher {
+    /// Create hasher that uses bcrypt with [`bcrypt::DEFAULT_COST`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create hasher that uses bcrypt with the given cost
+    pub fn bcrypt(cost: u32) -> Self {
+        PasswordHasher {
+            algorithm: HashAlgorithm::Bcrypt,
+            bcrypt_cost: cost,
+            ..Self::default()
+        }
+    }
+
+    /// Create hasher that uses Argon2id with the given parameters
+    pub fn argon2(params: Argon2Params) -> Self {
+        PasswordHasher {
+            algorithm: HashAlgorithm::Argon2,
+            argon2: params,
+            ..Self::default()
+        }
+    }
+
+    /// Create hasher that uses scrypt with the given parameters
+    pub fn scrypt(params: ScryptParams) -> Self {
+        PasswordHasher {
+            algorithm: HashAlgorithm::Scrypt,
+            scrypt: params,
+            ..Self::default()
+        }
+    }
+
+    /// Create hasher that uses PBKDF2-SHA256 (Django format) with the given iterations
+    pub fn pbkdf2(iterations: u32) -> Self {
+        PasswordHasher {
+            algorithm: HashAlgorithm::Pbkdf2,
+            pbkdf2_iterations: iterations,
+            ..Self::default()
+        }
+    }
+
+    /// Create builder to configure the algorithm and cost parameters
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use pn_utils::password::{HashAlgorithm, PasswordHasher};
+    ///
+    /// let hasher = PasswordHasher::builder()
+    ///     .algorithm(HashAlgorithm::Argon2)
+    ///     .memory_cost(1024)
+    ///     .iterations(1)
+    ///     .build()
+    ///     .expect("invalid parameters");
+    /// let hashed = hasher.hash("secret password".to_string());
+    /// ```
+    pub fn builder() -> PasswordHasherBuilder {
+        PasswordHasherBuilder {
+            hasher: Self::default(),
+        }
+    }
+
+    /// Algorithm used for new hashes
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// bcrypt cost used for new hashes
+    pub fn bcrypt_cost(&self) -> u32 {
+        self.bcrypt_cost
+    }
+
+    /// Argon2id parameters used for new hashes
+    pub fn argon2_params(&self) -> Argon2Params {
+        self.argon2
+    }
+
+    /// scrypt parameters used for new hashes
+    pub fn scrypt_params(&self) -> ScryptParams {
+        self.scrypt
+    }
+
+    /// PBKDF2-SHA256 iterations used for new hashes
+    pub fn pbkdf2_iterations(&self) -> u32 {
+        self.pbkdf2_iterations
+    }
+
+    /// Pepper applied before hashing, if any
+    pub fn pepper(&self) -> Option<&Pepper> {
+        self.pepper.as_ref()
+    }
+
+    /// Whether bcrypt passwords longer than 72 bytes are pre-hashed
+    pub fn prehash(&self) -> bool {
+        self.prehash
+    }
+
+    /// Whether passwords are normalized to NFKC before hashing
+    pub fn normalize(&self) -> bool {
+        self.normalize
+    }
+
+    /// Hashes the password with the configured algorithm
+    ///
+    /// The password is normalized first if enabled, then the current pepper is applied if any.
+    /// See [`normalize_password`] and [`Pepper`]
+    pub fn hash(&self, password: String) -> Result<String, PasswordError> {
+        let password = if self.normalize {
+            normalize_password(&password)
+        } else {
+            password
+        };
+        let password = match &self.pepper {
+            Some(pepper) => pepper.apply_current(&password),
+            None => password,
+        };
+
+        let hashed = match self.algorithm {
+            HashAlgorithm::Bcrypt if self.prehash => {
+                hash_bcrypt_prehashed(password, self.bcrypt_cost)?
+            }
+            HashAlgorithm::Bcrypt => bcrypt::hash(password, self.bcrypt_cost)?,
+            HashAlgorithm::Argon2 => hash_password_argon2(password, self.argon2)?,
+            HashAlgorithm::Scrypt => hash_password_scrypt(password, self.scrypt)?,
+            HashAlgorithm::Pbkdf2 => hash_password_pbkdf2(password, self.pbkdf2_iterations)?,
+        };
+
+        match &self.pepper {
+            Some(pepper) => Ok(pepper.wrap(&hashed)),
+            None => Ok(hashed),
+        }
+    }
+
+    /// Compares a password with a hashed password. See [`compare_password`](super::compare_password)
+    ///
+    /// Peppered hashes are verified with [`verify_password_peppered`], so hashes made before
+    /// the pepper was configured are still valid. Likewise, when normalization is enabled the
+    /// password as typed is also tried.
+    ///
+    /// Malformed hashes are treated as a wrong password, use
+    /// [`PasswordHasher::verify_and_upgrade`] to tell them apart.
+    pub fn verify(&self, password: String, hashed_password: String) -> bool {
+        self.check(password, &hashed_password)
+            .is_ok_and(|(valid, _)| valid)
+    }
+
+    /// Returns (valid, matched only without normalization)
+    fn check(
+        &self,
+        password: String,
+        hashed_password: &str,
+    ) -> Result<(bool, bool), PasswordError> {
+        if self.normalize {
+            let normalized = normalize_password(&password);
+            if normalized != password {
+                if self.verify_exact(normalized, hashed_password)? {
+                    return Ok((true, false));
+                }
+                // Hash dibuat sebelum normalisasi diaktifkan
+                return Ok((self.verify_exact(password, hashed_password)?, true));
+            }
+        }
+        Ok((self.verify_exact(password, hashed_password)?, false))
+    }
+
+    fn verify_exact(&self, password: String, hashed_password: &str) -> Result<bool, PasswordError> {
+        match &self.pepper {
+            Some(pepper) if split_peppered(hashed_password).is_some() => {
+                verify_password_peppered(password, hashed_password.to_string(), pepper)
+            }
+            _ => verify_password(password, hashed_password.to_string()),
+        }
+    }
+
+    /// Spend the same time as [`PasswordHasher::verify`], without a stored hash
+    ///
+    /// Call this when the username is not found, so the response time does not reveal which
+    /// accounts exist. Hashing with the configured algorithm and cost takes as long as verifying
+    /// a hash made by this hasher. Always returns `false`.
+    pub fn dummy_verify(&self, password: String) -> bool {
+        let _ = self.hash(password);
+        false
+    }
+
+    /// Checks if the hashed password was made with a different algorithm, cost or pepper version
+    ///
+    /// Hashes with an unknown format always need rehash.
+    pub fn needs_rehash(&self, hashed_password: &str) -> bool {
+        let hashed_password = match &self.pepper {
+            Some(pepper) if pepper.needs_rotation(hashed_password) => return true,
+            Some(_) => split_peppered(hashed_password).map_or(hashed_password, |(_, inner)| inner),
+            None => hashed_password,
+        };
+
+        if HashAlgorithm::detect(hashed_password) != Some(self.algorithm) {
+            return true;
+        }
+
+        match self.algorithm {
+            HashAlgorithm::Bcrypt => bcrypt_cost(hashed_password) != Some(self.bcrypt_cost),
+            HashAlgorithm::Argon2 => argon2_params(hashed_password) != Some(self.argon2),
+            HashAlgorithm::Scrypt => scrypt_params(hashed_password) != Some(self.scrypt),
+            HashAlgorithm::Pbkdf2 => {
+                pbkdf2_iterations(hashed_password) != Some(self.pbkdf2_iterations)
+            }
+        }
+    }
+
+    /// Verify the password, and create a new hash if the stored hash is outdated
+    ///
+    /// `new_hash` is only set when the password is valid and [`PasswordHasher::needs_rehash`] is true,
+    /// or the hash was made before normalization was enabled. A malformed stored hash is an error,
+    /// see [`verify_password`].
+    pub fn verify_and_upgrade(
+        &self,
+        password: String,
+        hashed_password: String,
+    ) -> Result<PasswordVerification, PasswordError> {
+        let (valid, unnormalized) = self.check(password.clone(), &hashed_password)?;
+
+        let mut new_hash = None;
+        if valid && (unnormalized || self.needs_rehash(&hashed_password)) {
+            new_hash = Some(self.hash(password)?);
+        }
+
+        Ok(PasswordVerification { valid, new_hash })
+    }
+}
+
+/// Builder for [`PasswordHasher`]
+#[derive(Debug, Clone)]
+pub struct PasswordHasherBuilder {
+    hasher: PasswordHasher,
+}
+
+impl PasswordHasherBuilder {
+    /// Algorithm used for new hashes. Default: bcrypt
+    pub fn algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.hasher.algorithm = algorithm;
+        self
+    }
+
+    /// bcrypt cost, between 4 and 31. Default: [`bcrypt::DEFAULT_COST`]
+    pub fn bcrypt_cost(mut self, cost: u32) -> Self {
+        self.hasher.bcrypt_cost = cost;
+        self
+    }
+
+    /// All Argon2id parameters. Default: [`Argon2Params::default`]
+    pub fn argon2_params(mut self, params: Argon2Params) -> Self {
+        self.hasher.argon2 = params;
+        self
+    }
+
+    /// All scrypt parameters. Default: [`ScryptParams::default`]
+    pub fn scrypt_params(mut self, params: ScryptParams) -> Self {
+        self.hasher.scrypt = params;
+        self
+    }
+
+    /// PBKDF2-SHA256 iterations. Default: [`PBKDF2_DEFAULT_ITERATIONS`]
+    pub fn pbkdf2_iterations(mut self, iterations: u32) -> Self {
+        self.hasher.pbkdf2_iterations = iterations;
+        self
+    }
+
+    /// Apply a pepper before hashing. Default: no pepper
+    pub fn pepper(mut self, pepper: Pepper) -> Self {
+        self.hasher.pepper = Some(pepper);
+        self
+    }
+
+    /// Pre-hash bcrypt passwords longer than 72 bytes with SHA-256. Default: `false`
+    ///
+    /// See [`crate::password::hash_password_prehashed`]. Only used with bcrypt.
+    pub fn prehash(mut self, enabled: bool) -> Self {
+        self.hasher.prehash = enabled;
+        self
+    }
+
+    /// Normalize passwords to Unicode NFKC before hashing. Default: `false`
+    ///
+    /// See [`normalize_password`]. Existing hashes of non-normalized passwords are still valid,
+    /// and are upgraded by [`PasswordHasher::verify_and_upgrade`].
+    pub fn normalize(mut self, enabled: bool) -> Self {
+        self.hasher.normalize = enabled;
+        self
+    }
+
+    /// Argon2id memory size in KiB
+    pub fn memory_cost(mut self, memory_cost: u32) -> Self {
+        self.hasher.argon2.memory_cost = memory_cost;
+        self
+    }
+
+    /// Argon2id number of iterations
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        self.hasher.argon2.iterations = iterations;
+        self
+    }
+
+    /// Argon2id degree of parallelism
+    pub fn parallelism(mut self, parallelism: u32) -> Self {
+        self.hasher.argon2.parallelism = parallelism;
+        self
+    }
+
+    /// Validate the parameters and create the hasher
+    pub fn build(self) -> Result<PasswordHasher, PasswordError> {
+        let cost = self.hasher.bcrypt_cost;
+        if !(BCRYPT_MIN_COST..=BCRYPT_MAX_COST).contains(&cost) {
+            return Err(BcryptError::CostNotAllowed(cost).into());
+        }
+
+        // Batas yang sama dengan verifikasi, supaya hash baru selalu bisa diverifikasi
+        let params = self.hasher.argon2;
+        check_argon2_params(params)?;
+        argon2::Params::new(
+            params.memory_cost,
+            params.iterations,
+            params.parallelism,
+            None,
+        )?;
+
+        let params = self.hasher.scrypt;
+        check_scrypt_params(params)?;
+        scrypt::Params::new(
+            params.log_n,
+            params.r,
+            params.p,
+            scrypt::Params::RECOMMENDED_LEN,
+        )
+        .map_err(|e| PasswordError::InvalidOptions(format!("scrypt: {}", e)))?;
+
+        check_pbkdf2_iterations(self.hasher.pbkdf2_iterations)?;
+
+        Ok(self.hasher)
+    }
+
+    /// Pick the cost that takes at least `target` to hash on the current machine, and create the hasher
+    ///
+    /// For bcrypt and scrypt the cost is raised until hashing reaches the target. For Argon2id the
+    /// memory size and parallelism are kept, and the number of iterations is estimated from a
+    /// single run, like the PBKDF2 iterations.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use pn_utils::password::PasswordHasher;
+    ///
+    /// let hasher = PasswordHasher::builder()
+    ///     .calibrate(Duration::from_millis(1))
+    ///     .expect("failed to calibrate");
+    /// println!("bcrypt cost: {}", hasher.bcrypt_cost());
+    /// ```
+    pub fn calibrate(mut self, target: Duration) -> Result<PasswordHasher, PasswordError> {
+        const SAMPLE: &str = "calibration password";
+
+        match self.hasher.algorithm {
+            HashAlgorithm::Bcrypt => {
+                let mut cost = BCRYPT_MIN_COST;
+                loop {
+                    let start = Instant::now();
+                    bcrypt::hash(SAMPLE, cost)?;
+                    // Setiap kenaikan cost menggandakan waktu hash
+                    if start.elapsed() >= target || cost >= BCRYPT_MAX_COST {
+                        break;
+                    }
+                    cost += 1;
+                }
+                self.hasher.bcrypt_cost = cost;
+            }
+            HashAlgorithm::Argon2 => {
+                let params = Argon2Params {
+                    iterations: 1,
+                    ..self.hasher.argon2
+                };
+                let start = Instant::now();
+                hash_password_argon2(SAMPLE.to_string(), params)?;
+                let elapsed = start.elapsed().as_secs_f64().max(f64::EPSILON);
+
+                let iterations = (target.as_secs_f64() / elapsed).ceil();
+                self.hasher.argon2.iterations =
+                    iterations.clamp(1.0, ARGON2_MAX_ITERATIONS as f64) as u32;
+            }
+            HashAlgorithm::Scrypt => {
+                let mut params = ScryptParams {
+                    log_n: 10,
+                    ..self.hasher.scrypt
+                };
+                loop {
+                    let start = Instant::now();
+                    hash_password_scrypt(SAMPLE.to_string(), params)?;
+                    // Batasi memori, N = 2^20 dengan r = 8 sudah 1 GiB
+                    let next = ScryptParams {
+                        log_n: params.log_n + 1,
+                        ..params
+                    };
+                    if start.elapsed() >= target
+                        || params.log_n >= SCRYPT_MAX_LOG_N
+                        || check_scrypt_params(next).is_err()
+                    {
+                        break;
+                    }
+                    params = next;
+                }
+                self.hasher.scrypt = params;
+            }
+            HashAlgorithm::Pbkdf2 => {
+                const SAMPLE_ITERATIONS: u32 = 10_000;
+
+                let start = Instant::now();
+                hash_password_pbkdf2(SAMPLE.to_string(), SAMPLE_ITERATIONS)?;
+                let elapsed = start.elapsed().as_secs_f64().max(f64::EPSILON);
+
+                let iterations = (target.as_secs_f64() / elapsed * SAMPLE_ITERATIONS as f64).ceil();
+                self.hasher.pbkdf2_iterations =
+                    iterations.clamp(1.0, PBKDF2_MAX_ITERATIONS as f64) as u32;
+            }
+        }
+
+        self.build()
+    }
+}
+
+/// Read cost from bcrypt hash, e.g. `$2b$12$...` => 12
+fn bcrypt_cost(hashed_password: &str) -> Option<u32> {
+    let hashed_password = strip_prehash(hashed_password).unwrap_or(hashed_password);
+    hashed_password.split('$').nth(2)?.parse().ok()
+}
+
+/// Read Argon2id parameters from PHC string
+fn argon2_params(hashed_password: &str) -> Option<Argon2Params> {
+    use argon2::password_hash::PasswordHash;
+    use argon2::{Params, Version, ARGON2ID_IDENT};
+
+    let parsed = PasswordHash::new(hashed_password).ok()?;
+    if parsed.algorithm != ARGON2ID_IDENT || parsed.version != Some(Version::V0x13.into()) {
+        return None;
+    }
+
+    let params = Params::try_from(&parsed).ok()?;
+    Some(Argon2Params {
+        memory_cost: params.m_cost(),
+        iterations: params.t_cost(),
+        parallelism: params.p_cost(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::password::compare_password;
+
+    const ARGON2_TEST_PARAMS: Argon2Params = Argon2Params {
+        memory_cost: 1024,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    #[test]
+    fn test_builder() {
+        let hasher = PasswordHasher::builder()
+            .bcrypt_cost(4)
+            .build()
+            .expect("failed to build hasher");
+        assert_eq!(hasher, PasswordHasher::bcrypt(4));
+
+        let hasher = PasswordHasher::builder()
+            .algorithm(HashAlgorithm::Argon2)
+            .memory_cost(1024)
+            .iterations(1)
+            .parallelism(1)
+            .build()
+            .expect("failed to build hasher");
+        assert_eq!(hasher, PasswordHasher::argon2(ARGON2_TEST_PARAMS));
+
+        assert!(PasswordHasher::builder().bcrypt_cost(3).build().is_err());
+        assert!(PasswordHasher::builder().bcrypt_cost(32).build().is_err());
+        assert!(PasswordHasher::builder().iterations(0).build().is_err());
+
+        // Parameter yang tidak bisa diverifikasi lagi ditolak
+        let cases = vec![
+            PasswordHasher::builder().iterations(ARGON2_MAX_ITERATIONS + 1),
+            PasswordHasher::builder().pbkdf2_iterations(PBKDF2_MAX_ITERATIONS + 1),
+            PasswordHasher::builder().scrypt_params(ScryptParams {
+                log_n: SCRYPT_MAX_LOG_N + 1,
+                ..ScryptParams::default()
+            }),
+        ];
+        for builder in cases {
+            let got = builder.clone().build();
+            assert!(
+                matches!(got, Err(PasswordError::InvalidOptions(_))),
+                "{:?}.build() should be invalid options, got {:?}",
+                builder,
+                got
+            );
+        }
+    }
+
+    #[test]
+    fn test_calibrate() {
+        let hasher = PasswordHasher::builder()
+            .calibrate(Duration::ZERO)
+            .expect("failed to calibrate");
+        assert_eq!(hasher.bcrypt_cost(), BCRYPT_MIN_COST);
+
+        let hasher = PasswordHasher::builder()
+            .algorithm(HashAlgorithm::Argon2)
+            .argon2_params(ARGON2_TEST_PARAMS)
+            .calibrate(Duration::from_millis(5))
+            .expect("failed to calibrate");
+        assert_eq!(hasher.argon2_params().memory_cost, 1024);
+        assert!(hasher.argon2_params().iterations >= 1);
+    }
+
+    #[test]
+    fn test_needs_rehash() {
+        let old = bcrypt::hash("password", 4).expect("failed to hashed password");
+
+        assert!(!PasswordHasher::bcrypt(4).needs_rehash(&old));
+        assert!(PasswordHasher::bcrypt(5).needs_rehash(&old));
+        assert!(PasswordHasher::argon2(ARGON2_TEST_PARAMS).needs_rehash(&old));
+        assert!(PasswordHasher::bcrypt(4).needs_rehash("5f4dcc3b5aa765d61d8327deb882cf99"));
+
+        let argon = hash_password_argon2("password".to_string(), ARGON2_TEST_PARAMS)
+            .expect("failed to hashed password");
+        assert!(!PasswordHasher::argon2(ARGON2_TEST_PARAMS).needs_rehash(&argon));
+        assert!(PasswordHasher::argon2(Argon2Params {
+            iterations: 2,
+            ..ARGON2_TEST_PARAMS
+        })
+        .needs_rehash(&argon));
+        assert!(PasswordHasher::bcrypt(4).needs_rehash(&argon));
+    }
+
+    #[test]
+    fn test_verify_and_upgrade() {
+        let password = "my_secret_password";
+        let old = bcrypt::hash(password, 4).expect("failed to hashed password");
+
+        // Up to date, no new hash
+        let result = PasswordHasher::bcrypt(4)
+            .verify_and_upgrade(password.to_string(), old.clone())
+            .expect("failed to verify");
+        assert_eq!(
+            result,
+            PasswordVerification {
+                valid: true,
+                new_hash: None
+            }
+        );
+
+        // Wrong password is never upgraded
+        let result = PasswordHasher::bcrypt(5)
+            .verify_and_upgrade("wrong_password".to_string(), old.clone())
+            .expect("failed to verify");
+        assert!(!result.valid);
+        assert_eq!(result.new_hash, None);
+
+        // Switch to argon2
+        let hasher = PasswordHasher::argon2(ARGON2_TEST_PARAMS);
+        let result = hasher
+            .verify_and_upgrade(password.to_string(), old.clone())
+            .expect("failed to verify");
+        assert!(result.valid);
+        let new_hash = result.new_hash.expect("hash should be upgraded");
+        assert!(!hasher.needs_rehash(&new_hash));
+        assert!(hasher.verify(password.to_string(), new_hash));
+
+        // Hash rusak adalah error, bukan password salah
+        let cases = vec![
+            "$2b$04$corrupted",
+            "$argon2id$v=19$m=1024",
+            "5f4dcc3b5aa765d61d8327deb882cf99",
+            "$pepper$v=1$2b$04$corrupted",
+        ];
+        for input in cases {
+            let got = hasher.verify_and_upgrade(password.to_string(), input.to_string());
+            assert!(
+                got.is_err(),
+                "verify_and_upgrade({:?}) should be error, got {:?}",
+                input,
+                got
+            );
+            assert!(!hasher.verify(password.to_string(), input.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_scrypt_and_pbkdf2() {
+        let params = ScryptParams {
+            log_n: 10,
+            ..ScryptParams::default()
+        };
+        let old = PasswordHasher::pbkdf2(1000)
+            .hash("my_secret_password".to_string())
+            .expect("failed to hashed password");
+        assert!(!PasswordHasher::pbkdf2(1000).needs_rehash(&old));
+        assert!(PasswordHasher::pbkdf2(2000).needs_rehash(&old));
+
+        // Pindah dari PBKDF2 ke scrypt
+        let hasher = PasswordHasher::scrypt(params);
+        assert!(hasher.needs_rehash(&old));
+        let result = hasher
+            .verify_and_upgrade("my_secret_password".to_string(), old)
+            .expect("failed to verify");
+        assert!(result.valid);
+        let new_hash = result.new_hash.expect("hash should be upgraded");
+        assert!(new_hash.starts_with("$scrypt$ln=10,r=8,p=1$"));
+        assert!(!hasher.needs_rehash(&new_hash));
+        assert!(PasswordHasher::scrypt(ScryptParams::default()).needs_rehash(&new_hash));
+
+        assert!(PasswordHasher::builder()
+            .pbkdf2_iterations(0)
+            .build()
+            .is_err());
+        assert!(PasswordHasher::builder()
+            .scrypt_params(ScryptParams { p: 0, ..params })
+            .build()
+            .is_err());
+
+        let hasher = PasswordHasher::builder()
+            .algorithm(HashAlgorithm::Pbkdf2)
+            .calibrate(Duration::ZERO)
+            .expect("failed to calibrate");
+        assert!(hasher.pbkdf2_iterations() >= 1);
+
+        // Kalibrasi tidak boleh melewati batas verifikasi
+        let hasher = PasswordHasher::builder()
+            .algorithm(HashAlgorithm::Pbkdf2)
+            .calibrate(Duration::from_secs(1_000_000))
+            .expect("failed to calibrate");
+        assert_eq!(hasher.pbkdf2_iterations(), PBKDF2_MAX_ITERATIONS);
+    }
+
+    #[test]
+    fn test_dummy_verify() {
+        assert!(!PasswordHasher::bcrypt(4).dummy_verify("my_secret_password".to_string()));
+        assert!(!PasswordHasher::argon2(ARGON2_TEST_PARAMS).dummy_verify(String::new()));
+    }
+
+    #[test]
+    fn test_pepper() {
+        let password = "my_secret_password";
+        let plain = bcrypt::hash(password, 4).expect("failed to hashed password");
+
+        let old = PasswordHasher::builder()
+            .bcrypt_cost(4)
+            .pepper(Pepper::new(1, b"old secret"))
+            .build()
+            .expect("failed to build hasher");
+        let hashed_v1 = old
+            .hash(password.to_string())
+            .expect("failed to hashed password");
+        assert!(hashed_v1.starts_with("$pepper$v=1$2b$04$"));
+        assert!(old.verify(password.to_string(), hashed_v1.clone()));
+        assert!(!old.needs_rehash(&hashed_v1));
+
+        // Hash tanpa pepper tetap valid, tapi harus di-rehash
+        assert!(old.verify(password.to_string(), plain.clone()));
+        assert!(old.needs_rehash(&plain));
+
+        // Rotasi pepper
+        let hasher = PasswordHasher::builder()
+            .bcrypt_cost(4)
+            .pepper(Pepper::new(2, b"new secret").with_previous(1, b"old secret"))
+            .build()
+            .expect("failed to build hasher");
+        assert!(hasher.needs_rehash(&hashed_v1));
+        let result = hasher
+            .verify_and_upgrade(password.to_string(), hashed_v1)
+            .expect("failed to verify");
+        assert!(result.valid);
+        let new_hash = result.new_hash.expect("hash should be upgraded");
+        assert_eq!(Pepper::version_of(&new_hash), Some(2));
+        assert!(!hasher.needs_rehash(&new_hash));
+        assert!(!compare_password(password.to_string(), new_hash));
+    }
+
+    #[test]
+    fn test_prehash_and_normalize() {
+        let hasher = PasswordHasher::builder()
+            .bcrypt_cost(4)
+            .prehash(true)
+            .normalize(true)
+            .build()
+            .expect("failed to build hasher");
+
+        // Lebih dari 72 byte, hanya berbeda di karakter terakhir
+        let long = "á".repeat(40);
+        let hashed = hasher
+            .hash(long.clone())
+            .expect("failed to hashed password");
+        assert!(hashed.starts_with("$sha256$2b$04$"));
+        assert!(!hasher.needs_rehash(&hashed));
+        assert!(hasher.verify(long.clone(), hashed.clone()));
+        assert!(!hasher.verify(format!("{}b", &long[..78]), hashed.clone()));
+
+        // Password pendek tidak diberi penanda
+        let hashed = hasher
+            .hash("password".to_string())
+            .expect("failed to hashed password");
+        assert!(hashed.starts_with("$2b$04$"));
+
+        // NFD dan NFC dianggap sama
+        let hashed = hasher
+            .hash("cafe\u{301}".to_string())
+            .expect("failed to hashed password");
+        assert!(hasher.verify("caf\u{e9}".to_string(), hashed));
+
+        // Hash lama tanpa normalisasi tetap valid dan di-upgrade
+        let old = bcrypt::hash("cafe\u{301}", 4).expect("failed to hashed password");
+        let result = hasher
+            .verify_and_upgrade("cafe\u{301}".to_string(), old)
+            .expect("failed to verify");
+        assert!(result.valid);
+        let new_hash = result.new_hash.expect("hash should be upgraded");
+        assert!(hasher.verify("caf\u{e9}".to_string(), new_hash));
+    }
+}
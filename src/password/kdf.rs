@@ -0,0 +1,385 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use super::{random_salt, PasswordError};
+use crate::constant_time_eq;
+use crate::helper::{nanoid_format, ALPHANUMERIC_CHARS};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use scrypt::password_hash::{Error, PasswordHash, PasswordHasher as _, PasswordVerifier as _};
+use scrypt::Scrypt;
+use sha2::Sha256;
+
+/// Prefix of PBKDF2-SHA256 hashes in Django format
+pub(crate) const PBKDF2_PREFIX: &str = "pbkdf2_sha256$";
+
+/// Default PBKDF2-SHA256 iterations, following the OWASP recommendation
+pub const PBKDF2_DEFAULT_ITERATIONS: u32 = 600_000;
+
+/// Length of the Django salt
+const PBKDF2_SALT_LENGTH: usize = 22;
+
+/// Highest PBKDF2 iterations accepted for new and stored hashes
+pub(crate) const PBKDF2_MAX_ITERATIONS: u32 = 10_000_000;
+
+/// Highest scrypt log2(N) accepted for new and stored hashes
+pub(crate) const SCRYPT_MAX_LOG_N: u8 = 20;
+
+/// Highest scrypt memory cost (128 * r * N bytes) accepted for new and stored hashes, 1 GiB
+const SCRYPT_MAX_MEMORY: u64 = 1 << 30;
+
+/// Highest scrypt parallelism accepted for new and stored hashes
+const SCRYPT_MAX_P: u32 = 16;
+
+/// Longest hash output accepted from a stored hash, in bytes
+pub(crate) const MAX_OUTPUT_LENGTH: usize = 64;
+
+/// scrypt cost parameters
+///
+/// Default values follow the scrypt crate recommendation (N = 2^17, r = 8, p = 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScryptParams {
+    /// log2 of the work factor N
+    pub log_n: u8,
+    /// Block size
+    pub r: u32,
+    /// Degree of parallelism
+    pub p: u32,
+}
+
+impl Default for ScryptParams {
+    fn default() -> Self {
+        ScryptParams {
+            log_n: scrypt::Params::RECOMMENDED_LOG_N,
+            r: scrypt::Params::RECOMMENDED_R,
+            p: scrypt::Params::RECOMMENDED_P,
+        }
+    }
+}
+
+/// Hashes the password using scrypt
+///
+/// Returns a PHC string, e.g. `$scrypt$ln=17,r=8,p=1$<salt>$<hash>`
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{compare_password, hash_password_scrypt, ScryptParams};
+///
+/// let params = ScryptParams { log_n: 10, ..ScryptParams::default() };
+/// let hashed = hash_password_scrypt("this is secret password".to_string(), params).unwrap();
+/// assert!(hashed.starts_with("$scrypt$ln=10,r=8,p=1$"));
+/// assert!(compare_password("this is secret password".to_string(), hashed));
+/// ```
+pub fn hash_password_scrypt(
+    password: String,
+    params: ScryptParams,
+) -> Result<String, PasswordError> {
+    check_scrypt_params(params)?;
+    let params = scrypt::Params::new(
+        params.log_n,
+        params.r,
+        params.p,
+        scrypt::Params::RECOMMENDED_LEN,
+    )
+    .map_err(|e| PasswordError::InvalidOptions(format!("scrypt: {}", e)))?;
+
+    let salt = random_salt()?;
+    let hashed = Scrypt
+        .hash_password_customized(password.as_bytes(), None, None, params, &salt)
+        .map_err(|e| PasswordError::InvalidOptions(format!("scrypt: {}", e)))?;
+    Ok(hashed.to_string())
+}
+
+/// Hashes the password using PBKDF2-SHA256 in Django format
+///
+/// Returns `pbkdf2_sha256$<iterations>$<salt>$<base64 hash>`, which Django can verify as is.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{compare_password, hash_password_pbkdf2};
+///
+/// let hashed = hash_password_pbkdf2("this is secret password".to_string(), 1000).unwrap();
+/// assert!(hashed.starts_with("pbkdf2_sha256$1000$"));
+/// assert!(compare_password("this is secret password".to_string(), hashed));
+/// ```
+pub fn hash_password_pbkdf2(password: String, iterations: u32) -> Result<String, PasswordError> {
+    check_pbkdf2_iterations(iterations)?;
+
+    let salt = nanoid_format(&ALPHANUMERIC_CHARS, PBKDF2_SALT_LENGTH);
+    let mut hash = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt.as_bytes(), iterations, &mut hash);
+
+    Ok(format!(
+        "{}{}${}${}",
+        PBKDF2_PREFIX,
+        iterations,
+        salt,
+        STANDARD.encode(hash)
+    ))
+}
+
+pub(crate) fn verify_scrypt(password: &str, hashed_password: &str) -> Result<bool, PasswordError> {
+    let parsed = PasswordHash::new(hashed_password)
+        .map_err(|e| PasswordError::MalformedHash(e.to_string()))?;
+    let Some(output) = parsed.hash else {
+        return Err(PasswordError::MalformedHash("missing hash".to_string()));
+    };
+
+    // Parameter dari hash yang rusak bisa membuat verifikasi berjalan sangat lama
+    let params = scrypt_params(hashed_password)
+        .ok_or_else(|| PasswordError::MalformedHash("scrypt: invalid parameters".to_string()))?;
+    check_scrypt_params(params)?;
+    if output.len() > MAX_OUTPUT_LENGTH {
+        return Err(PasswordError::InvalidOptions(
+            "scrypt: hash of the stored hash is too long".to_string(),
+        ));
+    }
+
+    match Scrypt.verify_password(password.as_bytes(), &parsed) {
+        Ok(()) => Ok(true),
+        Err(Error::Password) => Ok(false),
+        Err(e) => Err(PasswordError::MalformedHash(e.to_string())),
+    }
+}
+
+pub(crate) fn verify_pbkdf2(password: &str, hashed_password: &str) -> Result<bool, PasswordError> {
+    let (iterations, salt, expected) = parse_pbkdf2(hashed_password)?;
+    check_pbkdf2_iterations(iterations)?;
+    if expected.len() > MAX_OUTPUT_LENGTH {
+        return Err(PasswordError::InvalidOptions(
+            "pbkdf2: hash of the stored hash is too long".to_string(),
+        ));
+    }
+
+    let mut computed = vec![0u8; expected.len()];
+    pbkdf2::pbkdf2_hmac::<Sha256>(
+        password.as_bytes(),
+        salt.as_bytes(),
+        iterations,
+        &mut computed,
+    );
+    Ok(constant_time_eq(&computed, &expected))
+}
+
+/// Reject scrypt parameters outside the limits, so every new hash can be verified again
+pub(crate) fn check_scrypt_params(params: ScryptParams) -> Result<(), PasswordError> {
+    let memory = (128 * u64::from(params.r)).checked_shl(u32::from(params.log_n));
+    if params.log_n > SCRYPT_MAX_LOG_N
+        || memory.is_none_or(|m| m > SCRYPT_MAX_MEMORY)
+        || params.p > SCRYPT_MAX_P
+    {
+        return Err(PasswordError::InvalidOptions(format!(
+            "scrypt: log_n must be at most {}, memory at most {} bytes, and p at most {}",
+            SCRYPT_MAX_LOG_N, SCRYPT_MAX_MEMORY, SCRYPT_MAX_P
+        )));
+    }
+    Ok(())
+}
+
+/// Reject PBKDF2 iterations outside the limits, so every new hash can be verified again
+pub(crate) fn check_pbkdf2_iterations(iterations: u32) -> Result<(), PasswordError> {
+    if !(1..=PBKDF2_MAX_ITERATIONS).contains(&iterations) {
+        return Err(PasswordError::InvalidOptions(format!(
+            "pbkdf2: iterations must be between 1 and {}",
+            PBKDF2_MAX_ITERATIONS
+        )));
+    }
+    Ok(())
+}
+
+/// Read scrypt parameters from PHC string
+pub(crate) fn scrypt_params(hashed_password: &str) -> Option<ScryptParams> {
+    let parsed = PasswordHash::new(hashed_password).ok()?;
+    let params = scrypt::Params::try_from(&parsed).ok()?;
+    Some(ScryptParams {
+        log_n: params.log_n(),
+        r: params.r(),
+        p: params.p(),
+    })
+}
+
+/// Read iterations from Django PBKDF2 hash
+pub(crate) fn pbkdf2_iterations(hashed_password: &str) -> Option<u32> {
+    parse_pbkdf2(hashed_password)
+        .ok()
+        .map(|(iterations, _, _)| iterations)
+}
+
+/// Parse `pbkdf2_sha256$<iterations>$<salt>$<hash>` into (iterations, salt, hash)
+fn parse_pbkdf2(hashed_password: &str) -> Result<(u32, &str, Vec<u8>), PasswordError> {
+    let malformed = |reason: &str| PasswordError::MalformedHash(format!("pbkdf2: {}", reason));
+
+    let rest = hashed_password
+        .strip_prefix(PBKDF2_PREFIX)
+        .ok_or_else(|| malformed("missing prefix"))?;
+    let mut parts = rest.splitn(3, '$');
+    let (Some(iterations), Some(salt), Some(hash)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(malformed("missing fields"));
+    };
+
+    let iterations: u32 = iterations
+        .parse()
+        .ok()
+        .filter(|&i| i > 0)
+        .ok_or_else(|| malformed("invalid iterations"))?;
+    let hash = STANDARD
+        .decode(hash)
+        .ok()
+        .filter(|h| !h.is_empty())
+        .ok_or_else(|| malformed("invalid hash"))?;
+
+    Ok((iterations, salt, hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Dibuat dengan hashlib Python, password: "password"
+    const DJANGO_HASH: &str =
+        "pbkdf2_sha256$1000$seasalt1234$iX6D1OoXKWsmC82FxfLWOA73XMoClH4DFhojI/+rn44=";
+    const SCRYPT_HASH: &str =
+        "$scrypt$ln=10,r=8,p=1$MDEyMzQ1Njc4OWFiY2RlZg$ZEBCzLptWM7dhpNJDU2HbQ945ovKHmVEozHkePPbSqw";
+
+    #[test]
+    fn test_verify_known_hashes() {
+        assert_eq!(verify_pbkdf2("password", DJANGO_HASH).ok(), Some(true));
+        assert_eq!(
+            verify_pbkdf2("wrong_password", DJANGO_HASH).ok(),
+            Some(false)
+        );
+        assert_eq!(verify_scrypt("password", SCRYPT_HASH).ok(), Some(true));
+        assert_eq!(
+            verify_scrypt("wrong_password", SCRYPT_HASH).ok(),
+            Some(false)
+        );
+
+        assert_eq!(pbkdf2_iterations(DJANGO_HASH), Some(1000));
+        assert_eq!(
+            scrypt_params(SCRYPT_HASH),
+            Some(ScryptParams {
+                log_n: 10,
+                r: 8,
+                p: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_hash_and_verify() {
+        let hashed = hash_password_pbkdf2("my_secret_password".to_string(), 1000)
+            .expect("failed to hashed password");
+        assert_eq!(
+            verify_pbkdf2("my_secret_password", &hashed).ok(),
+            Some(true)
+        );
+        assert_eq!(verify_pbkdf2("wrong_password", &hashed).ok(), Some(false));
+
+        let params = ScryptParams {
+            log_n: 10,
+            ..ScryptParams::default()
+        };
+        let hashed = hash_password_scrypt("my_secret_password".to_string(), params)
+            .expect("failed to hashed password");
+        assert_eq!(scrypt_params(&hashed), Some(params));
+        assert_eq!(
+            verify_scrypt("my_secret_password", &hashed).ok(),
+            Some(true)
+        );
+
+        assert!(hash_password_pbkdf2("password".to_string(), 0).is_err());
+        assert!(
+            hash_password_scrypt("password".to_string(), ScryptParams { p: 0, ..params }).is_err()
+        );
+
+        // Hash yang tidak bisa diverifikasi tidak boleh dibuat
+        let got = hash_password_pbkdf2("password".to_string(), PBKDF2_MAX_ITERATIONS + 1);
+        assert!(
+            matches!(got, Err(PasswordError::InvalidOptions(_))),
+            "got {:?}",
+            got
+        );
+        let cases = vec![
+            ScryptParams {
+                log_n: SCRYPT_MAX_LOG_N + 1,
+                ..params
+            },
+            ScryptParams {
+                log_n: SCRYPT_MAX_LOG_N,
+                r: 16,
+                p: 1,
+            },
+            ScryptParams { p: 17, ..params },
+        ];
+        for input in cases {
+            let got = hash_password_scrypt("password".to_string(), input);
+            assert!(
+                matches!(got, Err(PasswordError::InvalidOptions(_))),
+                "hash_password_scrypt({:?}) should be invalid options, got {:?}",
+                input,
+                got
+            );
+        }
+    }
+
+    #[test]
+    fn test_malformed() {
+        let cases = vec![
+            "pbkdf2_sha256$",
+            "pbkdf2_sha256$1000$salt",
+            "pbkdf2_sha256$0$salt$iX6D1OoXKWsmC82FxfLWOA73XMoClH4DFhojI/+rn44=",
+            "pbkdf2_sha256$abc$salt$iX6D1OoXKWsmC82FxfLWOA73XMoClH4DFhojI/+rn44=",
+            "pbkdf2_sha256$1000$salt$not base64",
+            "pbkdf2_sha256$1000$salt$",
+        ];
+        for input in cases {
+            let got = verify_pbkdf2("password", input);
+            assert!(
+                got.is_err(),
+                "verify_pbkdf2({:?}) should be error, got {:?}",
+                input,
+                got
+            );
+        }
+
+        assert!(verify_scrypt("password", "$scrypt$ln=10,r=8,p=1").is_err());
+
+        // Parameter terlalu besar ditolak sebelum menghitung hash
+        let cases = vec![
+            "pbkdf2_sha256$4294967295$salt$iX6D1OoXKWsmC82FxfLWOA73XMoClH4DFhojI/+rn44="
+                .to_string(),
+            format!("pbkdf2_sha256$1000$salt${}", STANDARD.encode([0u8; 65])),
+        ];
+        for input in cases {
+            let got = verify_pbkdf2("password", &input);
+            assert!(
+                matches!(got, Err(PasswordError::InvalidOptions(_))),
+                "verify_pbkdf2({:?}) should be invalid options, got {:?}",
+                input,
+                got
+            );
+        }
+        let cases = vec![
+            "$scrypt$ln=30,r=8,p=1$MDEyMzQ1Njc4OWFiY2RlZg$ZEBCzLptWM7dhpNJDU2HbQ945ovKHmVEozHkePPbSqw",
+            "$scrypt$ln=20,r=16,p=1$MDEyMzQ1Njc4OWFiY2RlZg$ZEBCzLptWM7dhpNJDU2HbQ945ovKHmVEozHkePPbSqw",
+            "$scrypt$ln=10,r=8,p=64$MDEyMzQ1Njc4OWFiY2RlZg$ZEBCzLptWM7dhpNJDU2HbQ945ovKHmVEozHkePPbSqw",
+        ];
+        for input in cases {
+            let got = verify_scrypt("password", input);
+            assert!(
+                matches!(got, Err(PasswordError::InvalidOptions(_))),
+                "verify_scrypt({:?}) should be invalid options, got {:?}",
+                input,
+                got
+            );
+        }
+        assert!(verify_scrypt("password", "$scrypt$ln=x$c2FsdA$aGFzaA").is_err());
+    }
+}
@@ -0,0 +1,393 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use super::{PasswordError, PasswordHasher, PasswordVerification};
+use crate::constant_time_eq;
+use md5::Md5;
+use sha1::{Digest as _, Sha1};
+
+/// Prefix of legacy digests wrapped inside bcrypt
+const WRAPPED_PREFIX: &str = "$legacy$";
+
+/// Legacy password hash format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegacyFormat {
+    /// bcrypt from PHP `password_hash` (`$2y$`)
+    PhpBcrypt,
+    /// MD5 hex digest, unsalted or `md5$<salt>$<digest>`
+    Md5,
+    /// SHA-1 hex digest, unsalted or `sha1$<salt>$<digest>`
+    Sha1,
+    /// MD5 or SHA-1 digest wrapped inside bcrypt, e.g. `$legacy$md5$<salt>$2b$12$...`
+    Wrapped,
+}
+
+impl LegacyFormat {
+    /// Detect the legacy format of a hashed password
+    ///
+    /// Returns `None` for hashes that [`crate::password::compare_password`] already handles.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use pn_utils::password::LegacyFormat;
+    ///
+    /// let format = LegacyFormat::detect("md5$abc123$0dea94c6f1963eef9b8d224447a7169a");
+    /// assert_eq!(format, Some(LegacyFormat::Md5));
+    /// ```
+    pub fn detect(hashed_password: &str) -> Option<Self> {
+        if hashed_password.starts_with("$2y$") {
+            return Some(LegacyFormat::PhpBcrypt);
+        }
+        if parse_wrapped(hashed_password).is_some() {
+            return Some(LegacyFormat::Wrapped);
+        }
+        match parse_digest(hashed_password)?.0 {
+            DigestKind::Md5 => Some(LegacyFormat::Md5),
+            DigestKind::Sha1 => Some(LegacyFormat::Sha1),
+        }
+    }
+}
+
+/// Position of the salt in salted MD5/SHA-1 hashes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SaltPosition {
+    /// `digest(salt + password)`
+    #[default]
+    Prefix,
+    /// `digest(password + salt)`
+    Suffix,
+}
+
+/// Verifier for legacy password hashes
+///
+/// Recognises PHP `$2y$` bcrypt, salted or unsalted MD5/SHA-1 hex digests, and digests wrapped
+/// inside bcrypt with [`LegacyVerifier::wrap`]. Every legacy hash is flagged for upgrade, and
+/// other hashes are passed to the configured [`PasswordHasher`].
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{LegacyVerifier, PasswordHasher};
+///
+/// let verifier = LegacyVerifier::new().hasher(PasswordHasher::bcrypt(4));
+/// let stored = "md5$abc123$0dea94c6f1963eef9b8d224447a7169a".to_string();
+///
+/// let result = verifier
+///     .verify_and_upgrade("password".to_string(), stored)
+///     .expect("failed to verify password");
+/// assert!(result.valid);
+///
+/// if let Some(new_hash) = result.new_hash {
+///     // save new_hash to database
+///     assert!(new_hash.starts_with("$2b$04$"));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LegacyVerifier {
+    hasher: PasswordHasher,
+    salt_position: SaltPosition,
+}
+
+impl LegacyVerifier {
+    /// Create verifier with the default [`PasswordHasher`] and salt as prefix
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hasher used for new hashes and for hashes that are not legacy
+    pub fn hasher(mut self, hasher: PasswordHasher) -> Self {
+        self.hasher = hasher;
+        self
+    }
+
+    /// Position of the salt in salted MD5/SHA-1 hashes. Default: [`SaltPosition::Prefix`]
+    pub fn salt_position(mut self, position: SaltPosition) -> Self {
+        self.salt_position = position;
+        self
+    }
+
+    /// Compares a password with a legacy or current hashed password
+    pub fn verify(&self, password: String, hashed_password: String) -> bool {
+        match LegacyFormat::detect(&hashed_password) {
+            Some(LegacyFormat::PhpBcrypt) => {
+                bcrypt::verify(password, &hashed_password).unwrap_or(false)
+            }
+            Some(LegacyFormat::Md5 | LegacyFormat::Sha1) => {
+                let Some((kind, salt, digest)) = parse_digest(&hashed_password) else {
+                    return false;
+                };
+                let computed = kind.digest(&self.salted(&password, salt));
+                constant_time_eq(computed.as_bytes(), digest.to_ascii_lowercase().as_bytes())
+            }
+            Some(LegacyFormat::Wrapped) => {
+                let Some((kind, salt, inner)) = parse_wrapped(&hashed_password) else {
+                    return false;
+                };
+                let computed = kind.digest(&self.salted(&password, salt));
+                bcrypt::verify(computed, inner).unwrap_or(false)
+            }
+            None => self.hasher.verify(password, hashed_password),
+        }
+    }
+
+    /// Checks if the hashed password is a legacy hash, or needs rehash with the configured hasher
+    pub fn needs_upgrade(&self, hashed_password: &str) -> bool {
+        LegacyFormat::detect(hashed_password).is_some() || self.hasher.needs_rehash(hashed_password)
+    }
+
+    /// Verify the password, and create a new hash if the stored hash is legacy or outdated
+    ///
+    /// `new_hash` is only set when the password is valid.
+    pub fn verify_and_upgrade(
+        &self,
+        password: String,
+        hashed_password: String,
+    ) -> Result<PasswordVerification, PasswordError> {
+        if LegacyFormat::detect(&hashed_password).is_none() {
+            return self.hasher.verify_and_upgrade(password, hashed_password);
+        }
+
+        let valid = self.verify(password.clone(), hashed_password);
+
+        let mut new_hash = None;
+        if valid {
+            new_hash = Some(self.hasher.hash(password)?);
+        }
+
+        Ok(PasswordVerification { valid, new_hash })
+    }
+
+    /// Wrap a MD5/SHA-1 hash inside bcrypt, without knowing the password
+    ///
+    /// Used to migrate every stored digest at once. The result is still verified by
+    /// [`LegacyVerifier::verify`] and replaced on the next login.
+    /// The bcrypt cost is taken from the configured hasher. Returns
+    /// [`PasswordError::InvalidOptions`] if the salt contains `$`, because the wrapped hash could
+    /// not be parsed again.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use pn_utils::password::{LegacyVerifier, PasswordHasher};
+    ///
+    /// let verifier = LegacyVerifier::new().hasher(PasswordHasher::bcrypt(4));
+    /// let wrapped = verifier
+    ///     .wrap("5f4dcc3b5aa765d61d8327deb882cf99")
+    ///     .expect("failed to wrap hash");
+    /// assert!(wrapped.starts_with("$legacy$md5$$2b$04$"));
+    /// assert!(verifier.verify("password".to_string(), wrapped));
+    /// ```
+    pub fn wrap(&self, legacy_hash: &str) -> Result<String, PasswordError> {
+        let Some((kind, salt, digest)) = parse_digest(legacy_hash) else {
+            return Err(PasswordError::InvalidOptions(
+                "only MD5 and SHA-1 hashes can be wrapped".to_string(),
+            ));
+        };
+        if salt.contains('$') {
+            return Err(PasswordError::InvalidOptions(
+                "salt of a wrapped hash must not contain '$'".to_string(),
+            ));
+        }
+
+        let inner = bcrypt::hash(digest.to_ascii_lowercase(), self.hasher.bcrypt_cost())?;
+        Ok(format!(
+            "{}{}${}{}",
+            WRAPPED_PREFIX,
+            kind.name(),
+            salt,
+            inner
+        ))
+    }
+
+    fn salted(&self, password: &str, salt: &str) -> String {
+        match self.salt_position {
+            SaltPosition::Prefix => format!("{}{}", salt, password),
+            SaltPosition::Suffix => format!("{}{}", password, salt),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DigestKind {
+    Md5,
+    Sha1,
+}
+
+impl DigestKind {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "md5" => Some(DigestKind::Md5),
+            "sha1" => Some(DigestKind::Sha1),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            DigestKind::Md5 => "md5",
+            DigestKind::Sha1 => "sha1",
+        }
+    }
+
+    /// Length of the hex digest
+    fn hex_len(self) -> usize {
+        match self {
+            DigestKind::Md5 => 32,
+            DigestKind::Sha1 => 40,
+        }
+    }
+
+    fn digest(self, input: &str) -> String {
+        match self {
+            DigestKind::Md5 => hex::encode(Md5::digest(input.as_bytes())),
+            DigestKind::Sha1 => hex::encode(Sha1::digest(input.as_bytes())),
+        }
+    }
+}
+
+/// Parse `<kind>$<salt>$<digest>` or a bare hex digest into (kind, salt, digest)
+fn parse_digest(hashed_password: &str) -> Option<(DigestKind, &str, &str)> {
+    let (kind, salt, digest) = match hashed_password.split_once('$') {
+        Some((name, rest)) => {
+            // Digest selalu bagian terakhir, salt boleh berisi `$`
+            let (salt, digest) = rest.rsplit_once('$')?;
+            (DigestKind::from_name(name)?, salt, digest)
+        }
+        // Tanpa salt, jenis digest dilihat dari panjangnya
+        None => match hashed_password.len() {
+            32 => (DigestKind::Md5, "", hashed_password),
+            40 => (DigestKind::Sha1, "", hashed_password),
+            _ => return None,
+        },
+    };
+
+    if digest.len() != kind.hex_len() || !digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some((kind, salt, digest))
+}
+
+/// Parse `$legacy$<kind>$<salt>$2b$...` into (kind, salt, bcrypt hash)
+fn parse_wrapped(hashed_password: &str) -> Option<(DigestKind, &str, &str)> {
+    let rest = hashed_password.strip_prefix(WRAPPED_PREFIX)?;
+    let (name, rest) = rest.split_once('$')?;
+    let end = rest.find('$')?;
+    Some((DigestKind::from_name(name)?, &rest[..end], &rest[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bcrypt::Version;
+
+    #[test]
+    fn test_detect() {
+        let php = bcrypt::hash_with_result("password", 4)
+            .expect("failed to hashed password")
+            .format_for_version(Version::TwoY);
+        let cases = vec![
+            (php.as_str(), Some(LegacyFormat::PhpBcrypt)),
+            ("5f4dcc3b5aa765d61d8327deb882cf99", Some(LegacyFormat::Md5)),
+            (
+                "md5$abc123$0dea94c6f1963eef9b8d224447a7169a",
+                Some(LegacyFormat::Md5),
+            ),
+            (
+                "sha1$abc123$4e8dc328ace9d8fd9048c40696461465bf16798b",
+                Some(LegacyFormat::Sha1),
+            ),
+            (
+                "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8",
+                Some(LegacyFormat::Sha1),
+            ),
+            ("$legacy$md5$abc123$2b$04$abc", Some(LegacyFormat::Wrapped)),
+            ("md5$abc123$0dea94c6f1963eef", None),
+            ("sha256$abc123$0dea94c6f1963eef9b8d224447a7169a", None),
+            ("$2b$04$abc", None),
+            ("not a hash", None),
+        ];
+        for (input, expected) in cases {
+            let got = LegacyFormat::detect(input);
+            assert_eq!(
+                got, expected,
+                "LegacyFormat::detect({:?}) should be {:?}, got {:?}",
+                input, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let verifier = LegacyVerifier::new().hasher(PasswordHasher::bcrypt(4));
+        let php = bcrypt::hash_with_result("password", 4)
+            .expect("failed to hashed password")
+            .format_for_version(Version::TwoY);
+
+        for hashed in [
+            php.as_str(),
+            "5f4dcc3b5aa765d61d8327deb882cf99",
+            "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8",
+            "md5$abc123$0dea94c6f1963eef9b8d224447a7169a",
+            "sha1$abc123$4e8dc328ace9d8fd9048c40696461465bf16798b",
+        ] {
+            assert!(
+                verifier.verify("password".to_string(), hashed.to_string()),
+                "{:?} should match",
+                hashed
+            );
+            assert!(!verifier.verify("wrong_password".to_string(), hashed.to_string()));
+            assert!(verifier.needs_upgrade(hashed));
+        }
+
+        // Salt di belakang password
+        let suffix = LegacyVerifier::new().salt_position(SaltPosition::Suffix);
+        assert!(suffix.verify(
+            "password".to_string(),
+            "md5$abc123$65d52541a1ab3c76dff3feacb6b0a456".to_string()
+        ));
+        assert!(!verifier.verify(
+            "password".to_string(),
+            "md5$abc123$65d52541a1ab3c76dff3feacb6b0a456".to_string()
+        ));
+
+        // Hash modern diteruskan ke hasher
+        let modern = bcrypt::hash("password", 4).expect("failed to hashed password");
+        assert!(verifier.verify("password".to_string(), modern.clone()));
+        assert!(!verifier.needs_upgrade(&modern));
+    }
+
+    #[test]
+    fn test_wrap() {
+        let verifier = LegacyVerifier::new().hasher(PasswordHasher::bcrypt(4));
+
+        let wrapped = verifier
+            .wrap("sha1$abc123$4e8dc328ace9d8fd9048c40696461465bf16798b")
+            .expect("failed to wrap hash");
+        assert!(wrapped.starts_with("$legacy$sha1$abc123$2b$04$"));
+        assert_eq!(LegacyFormat::detect(&wrapped), Some(LegacyFormat::Wrapped));
+        assert!(verifier.verify("password".to_string(), wrapped.clone()));
+        assert!(!verifier.verify("wrong_password".to_string(), wrapped.clone()));
+
+        let result = verifier
+            .verify_and_upgrade("password".to_string(), wrapped)
+            .expect("failed to verify");
+        assert!(result.valid);
+        let new_hash = result.new_hash.expect("hash should be upgraded");
+        assert!(!verifier.needs_upgrade(&new_hash));
+
+        assert!(verifier.wrap("$2b$04$abc").is_err());
+
+        // Salt dengan `$` bisa diverifikasi, tapi tidak bisa dibungkus
+        let dollar_salt = format!("md5$a$b${:x}", Md5::digest(b"a$bpassword"));
+        assert!(verifier.verify("password".to_string(), dollar_salt.clone()));
+        assert!(matches!(
+            verifier.wrap(&dollar_salt),
+            Err(PasswordError::InvalidOptions(_))
+        ));
+    }
+}
@@ -0,0 +1,34 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+mod breach;
+mod error;
+mod generator;
+mod hash;
+mod hasher;
+mod kdf;
+mod language;
+mod legacy;
+#[cfg(feature = "async")]
+mod nonblocking;
+mod pepper;
+mod policy;
+mod strength;
+
+pub use breach::*;
+pub use error::*;
+pub use generator::*;
+pub use hash::*;
+pub use hasher::*;
+pub use kdf::*;
+pub use language::*;
+pub use legacy::*;
+#[cfg(feature = "async")]
+pub use nonblocking::*;
+pub use pepper::*;
+pub use policy::*;
+pub use strength::*;
@@ -0,0 +1,247 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use super::{
+    compare_password, hash_password, verify_password, PasswordError, PasswordHasher,
+    PasswordVerification,
+};
+use lazy_static::lazy_static;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+lazy_static! {
+    /// Limiter shared by the free async functions
+    static ref DEFAULT_LIMITER: Arc<Semaphore> = Arc::new(Semaphore::new(default_concurrency()));
+}
+
+/// Default number of concurrent hashing tasks: the number of CPU cores
+pub fn default_concurrency() -> usize {
+    std::thread::available_parallelism().map_or(4, |n| n.get())
+}
+
+/// Async version of [`hash_password`]
+///
+/// Runs on the tokio blocking thread pool, with at most [`default_concurrency`] hashes at a time.
+/// Requires the `async` feature.
+///
+/// ## Example
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let hashed = pn_utils::password::hash_password_async("this is secret password".to_string()).await;
+/// println!("{}", hashed.unwrap_or("failed".to_string()));
+/// # }
+/// ```
+pub async fn hash_password_async(password: String) -> Result<String, PasswordError> {
+    Ok(run_blocking(&DEFAULT_LIMITER, move || hash_password(password)).await??)
+}
+
+/// Async version of [`compare_password`]. Requires the `async` feature
+pub async fn compare_password_async(password: String, hashed_password: String) -> bool {
+    run_blocking(&DEFAULT_LIMITER, move || {
+        compare_password(password, hashed_password)
+    })
+    .await
+    .unwrap_or(false)
+}
+
+/// Async version of [`verify_password`]. Requires the `async` feature
+pub async fn verify_password_async(
+    password: String,
+    hashed_password: String,
+) -> Result<bool, PasswordError> {
+    run_blocking(&DEFAULT_LIMITER, move || {
+        verify_password(password, hashed_password)
+    })
+    .await?
+}
+
+/// [`PasswordHasher`] that runs on the tokio blocking thread pool
+///
+/// At most `max_concurrency` hashes run at a time, other calls wait for a free slot without
+/// blocking the async runtime. Cloning is cheap and clones share the same limit, so one instance
+/// can be kept in the application state. Requires the `async` feature.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{AsyncPasswordHasher, PasswordHasher};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let hasher = AsyncPasswordHasher::new(PasswordHasher::bcrypt(4)).max_concurrency(2);
+///
+/// let hashed = hasher
+///     .hash("secret password".to_string())
+///     .await
+///     .expect("failed to hash password");
+/// assert!(hasher.verify("secret password".to_string(), hashed).await);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AsyncPasswordHasher {
+    hasher: Arc<PasswordHasher>,
+    limiter: Arc<Semaphore>,
+}
+
+impl Default for AsyncPasswordHasher {
+    fn default() -> Self {
+        Self::new(PasswordHasher::default())
+    }
+}
+
+impl AsyncPasswordHasher {
+    /// Create async hasher with [`default_concurrency`]
+    pub fn new(hasher: PasswordHasher) -> Self {
+        AsyncPasswordHasher {
+            hasher: Arc::new(hasher),
+            limiter: Arc::new(Semaphore::new(default_concurrency())),
+        }
+    }
+
+    /// Maximum number of hashes running at a time, at least 1
+    pub fn max_concurrency(mut self, max: usize) -> Self {
+        self.limiter = Arc::new(Semaphore::new(max.max(1)));
+        self
+    }
+
+    /// The wrapped hasher
+    pub fn hasher(&self) -> &PasswordHasher {
+        &self.hasher
+    }
+
+    /// See [`PasswordHasher::hash`]
+    pub async fn hash(&self, password: String) -> Result<String, PasswordError> {
+        let hasher = self.hasher.clone();
+        run_blocking(&self.limiter, move || hasher.hash(password)).await?
+    }
+
+    /// See [`PasswordHasher::verify`]
+    pub async fn verify(&self, password: String, hashed_password: String) -> bool {
+        let hasher = self.hasher.clone();
+        run_blocking(&self.limiter, move || {
+            hasher.verify(password, hashed_password)
+        })
+        .await
+        .unwrap_or(false)
+    }
+
+    /// See [`PasswordHasher::verify_and_upgrade`]
+    pub async fn verify_and_upgrade(
+        &self,
+        password: String,
+        hashed_password: String,
+    ) -> Result<PasswordVerification, PasswordError> {
+        let hasher = self.hasher.clone();
+        run_blocking(&self.limiter, move || {
+            hasher.verify_and_upgrade(password, hashed_password)
+        })
+        .await?
+    }
+
+    /// See [`PasswordHasher::dummy_verify`]
+    pub async fn dummy_verify(&self, password: String) -> bool {
+        let hasher = self.hasher.clone();
+        run_blocking(&self.limiter, move || hasher.dummy_verify(password))
+            .await
+            .unwrap_or(false)
+    }
+}
+
+/// Wait for a free slot, then run the closure on the blocking thread pool
+async fn run_blocking<T, F>(limiter: &Arc<Semaphore>, f: F) -> Result<T, PasswordError>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let permit = limiter
+        .clone()
+        .acquire_owned()
+        .await
+        .map_err(|e| PasswordError::Task(e.to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        // Slot baru dilepas setelah hash selesai, walaupun future-nya sudah dibatalkan
+        let _permit = permit;
+        f()
+    })
+    .await
+    .map_err(|e| PasswordError::Task(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_hash_and_compare_password_async() {
+        let password = "my_secret_password";
+        let hashed_password = hash_password_async(password.to_string())
+            .await
+            .expect("failed to hashed password");
+
+        assert!(compare_password_async(password.to_string(), hashed_password.clone()).await);
+        assert!(!compare_password_async("wrong_password".to_string(), hashed_password).await);
+        assert!(
+            verify_password_async(password.to_string(), "$2b$04$corrupted".to_string())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_hasher() {
+        let hasher = AsyncPasswordHasher::new(PasswordHasher::bcrypt(4));
+        let old = bcrypt::hash("my_secret_password", 5).expect("failed to hashed password");
+
+        let result = hasher
+            .verify_and_upgrade("my_secret_password".to_string(), old)
+            .await
+            .expect("failed to verify");
+        assert!(result.valid);
+        let new_hash = result.new_hash.expect("hash should be upgraded");
+        assert!(new_hash.starts_with("$2b$04$"));
+        assert!(
+            hasher
+                .verify("my_secret_password".to_string(), new_hash)
+                .await
+        );
+        assert!(!hasher.dummy_verify("my_secret_password".to_string()).await);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_max_concurrency() {
+        let limiter = Arc::new(Semaphore::new(2));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let limiter = limiter.clone();
+                let running = running.clone();
+                let peak = peak.clone();
+                tokio::spawn(async move {
+                    run_blocking(&limiter, move || {
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        std::thread::sleep(Duration::from_millis(20));
+                        running.fetch_sub(1, Ordering::SeqCst);
+                    })
+                    .await
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.expect("task panicked").expect("task failed");
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,271 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use super::{hash_password, verify_password, PasswordError, PBKDF2_PREFIX};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Prefix of peppered hashes, followed by the pepper version and the inner hash
+const PEPPER_PREFIX: &str = "$pepper$v=";
+
+/// Server-side secret applied to passwords before hashing
+///
+/// The password is replaced with `base64(HMAC-SHA256(pepper, password))` before bcrypt or Argon2,
+/// so a database leak alone is not enough to crack the hashes.
+///
+/// Several versions can be registered to rotate the pepper: new hashes always use the current
+/// version, and older versions are only used for verification.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{compare_password_peppered, hash_password_peppered, Pepper};
+///
+/// let pepper = Pepper::new(2, b"new server secret").with_previous(1, b"old server secret");
+///
+/// let hashed = hash_password_peppered("secret password".to_string(), &pepper).unwrap();
+/// assert!(hashed.starts_with("$pepper$v=2$2b$"));
+/// assert!(compare_password_peppered("secret password".to_string(), hashed, &pepper));
+/// ```
+#[derive(Clone)]
+pub struct Pepper {
+    current: u32,
+    secrets: BTreeMap<u32, Vec<u8>>,
+}
+
+impl fmt::Debug for Pepper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Jangan tampilkan secret
+        f.debug_struct("Pepper")
+            .field("current", &self.current)
+            .field("versions", &self.secrets.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl PartialEq for Pepper {
+    fn eq(&self, other: &Self) -> bool {
+        self.current == other.current && self.secrets == other.secrets
+    }
+}
+
+impl Eq for Pepper {}
+
+impl Pepper {
+    /// Create pepper with the current version and secret
+    pub fn new(version: u32, secret: &[u8]) -> Self {
+        let mut secrets = BTreeMap::new();
+        secrets.insert(version, secret.to_vec());
+        Pepper {
+            current: version,
+            secrets,
+        }
+    }
+
+    /// Register an older pepper, only used to verify existing hashes
+    pub fn with_previous(mut self, version: u32, secret: &[u8]) -> Self {
+        if version != self.current {
+            self.secrets.insert(version, secret.to_vec());
+        }
+        self
+    }
+
+    /// Version used for new hashes
+    pub fn current_version(&self) -> u32 {
+        self.current
+    }
+
+    /// Read the pepper version of a peppered hash
+    pub fn version_of(hashed_password: &str) -> Option<u32> {
+        split_peppered(hashed_password).map(|(version, _)| version)
+    }
+
+    /// Checks if the hash is not peppered with the current version
+    pub fn needs_rotation(&self, hashed_password: &str) -> bool {
+        Self::version_of(hashed_password) != Some(self.current)
+    }
+
+    /// Apply the current pepper to the password
+    pub(crate) fn apply_current(&self, password: &str) -> String {
+        self.apply(self.current, password)
+            .expect("current pepper is always registered")
+    }
+
+    /// Apply the pepper with the given version to the password
+    pub(crate) fn apply(&self, version: u32, password: &str) -> Option<String> {
+        let secret = self.secrets.get(&version)?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).ok()?;
+        mac.update(password.as_bytes());
+        Some(STANDARD.encode(mac.finalize().into_bytes()))
+    }
+
+    /// Add the version prefix to an inner hash
+    pub(crate) fn wrap(&self, inner_hash: &str) -> String {
+        // Hash Django tidak diawali `$`
+        let separator = if inner_hash.starts_with('$') { "" } else { "$" };
+        format!(
+            "{}{}{}{}",
+            PEPPER_PREFIX, self.current, separator, inner_hash
+        )
+    }
+}
+
+/// Split peppered hash into (version, inner hash)
+pub(crate) fn split_peppered(hashed_password: &str) -> Option<(u32, &str)> {
+    let rest = hashed_password.strip_prefix(PEPPER_PREFIX)?;
+    let end = rest.find('$')?;
+    let version = rest[..end].parse().ok()?;
+    let inner = &rest[end..];
+    match inner.strip_prefix('$') {
+        Some(django) if django.starts_with(PBKDF2_PREFIX) => Some((version, django)),
+        _ => Some((version, inner)),
+    }
+}
+
+/// Hashes the password using bcrypt, after applying the current pepper
+///
+/// The result is `$pepper$v=<version>` followed by the bcrypt hash.
+pub fn hash_password_peppered(password: String, pepper: &Pepper) -> Result<String, PasswordError> {
+    let inner = hash_password(pepper.apply_current(&password))?;
+    Ok(pepper.wrap(&inner))
+}
+
+/// Compares a password with a peppered hash
+///
+/// The pepper version is read from the hash, so hashes made with an older pepper are still valid.
+/// Returns `false` if the hash is not peppered, or its pepper version is unknown. Use
+/// [`verify_password_peppered`] to tell them apart from a wrong password.
+pub fn compare_password_peppered(
+    password: String,
+    hashed_password: String,
+    pepper: &Pepper,
+) -> bool {
+    verify_password_peppered(password, hashed_password, pepper).unwrap_or(false)
+}
+
+/// Compares a password with a peppered hash, returning an error for malformed hashes
+///
+/// Like [`verify_password`], `Ok(false)` means the password is wrong. Hashes without the
+/// `$pepper$` prefix are malformed, and an unknown pepper version is an invalid option.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{verify_password_peppered, Pepper};
+///
+/// let pepper = Pepper::new(1, b"server secret");
+/// let corrupted = "$pepper$v=1$2b$04$corrupted".to_string();
+/// assert!(verify_password_peppered("secret password".to_string(), corrupted, &pepper).is_err());
+/// ```
+pub fn verify_password_peppered(
+    password: String,
+    hashed_password: String,
+    pepper: &Pepper,
+) -> Result<bool, PasswordError> {
+    let Some((version, inner)) = split_peppered(&hashed_password) else {
+        return Err(PasswordError::MalformedHash(
+            "pepper: missing $pepper$ prefix".to_string(),
+        ));
+    };
+    match pepper.apply(version, &password) {
+        Some(peppered) => verify_password(peppered, inner.to_string()),
+        None => Err(PasswordError::InvalidOptions(format!(
+            "pepper: unknown version {}",
+            version
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::password::compare_password;
+
+    #[test]
+    fn test_hash_and_compare_password_peppered() {
+        let pepper = Pepper::new(1, b"server secret");
+        let hashed = hash_password_peppered("my_secret_password".to_string(), &pepper)
+            .expect("failed to hashed password");
+
+        assert_eq!(Pepper::version_of(&hashed), Some(1));
+        assert!(compare_password_peppered(
+            "my_secret_password".to_string(),
+            hashed.clone(),
+            &pepper
+        ));
+        assert!(!compare_password_peppered(
+            "wrong_password".to_string(),
+            hashed.clone(),
+            &pepper
+        ));
+
+        // Pepper yang berbeda
+        let other = Pepper::new(1, b"other secret");
+        assert!(!compare_password_peppered(
+            "my_secret_password".to_string(),
+            hashed.clone(),
+            &other
+        ));
+
+        // Hash tanpa pepper
+        assert!(!compare_password("my_secret_password".to_string(), hashed));
+    }
+
+    #[test]
+    fn test_rotation() {
+        let old = Pepper::new(1, b"old secret");
+        let hashed = hash_password_peppered("my_secret_password".to_string(), &old)
+            .expect("failed to hashed password");
+
+        let rotated = Pepper::new(2, b"new secret").with_previous(1, b"old secret");
+        assert!(rotated.needs_rotation(&hashed));
+        assert!(compare_password_peppered(
+            "my_secret_password".to_string(),
+            hashed.clone(),
+            &rotated
+        ));
+
+        // Versi lama sudah dihapus
+        let removed = Pepper::new(2, b"new secret");
+        assert!(!compare_password_peppered(
+            "my_secret_password".to_string(),
+            hashed.clone(),
+            &removed
+        ));
+        assert!(matches!(
+            verify_password_peppered("my_secret_password".to_string(), hashed, &removed),
+            Err(PasswordError::InvalidOptions(_))
+        ));
+    }
+
+    #[test]
+    fn test_split_peppered() {
+        let cases = vec![
+            ("$pepper$v=3$2b$12$abc", Some((3, "$2b$12$abc"))),
+            ("$pepper$v=10$argon2id$v=19", Some((10, "$argon2id$v=19"))),
+            ("$pepper$v=x$2b$12$abc", None),
+            (
+                "$pepper$v=2$pbkdf2_sha256$1000$salt$aGFzaA==",
+                Some((2, "pbkdf2_sha256$1000$salt$aGFzaA==")),
+            ),
+            ("$pepper$v=3", None),
+            ("$2b$12$abc", None),
+        ];
+        for (input, expected) in cases {
+            let got = split_peppered(input);
+            assert_eq!(
+                got, expected,
+                "split_peppered({:?}) should be {:?}, got {:?}",
+                input, expected, got
+            );
+        }
+    }
+}
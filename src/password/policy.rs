@@ -0,0 +1,404 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use super::{estimate_strength, BreachChecker, Language};
+use std::sync::Arc;
+
+/// bcrypt ignores input beyond 72 bytes
+pub const BCRYPT_MAX_PASSWORD_BYTES: usize = 72;
+
+/// Rule violated by a password. See [`PasswordPolicy::validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyViolation {
+    /// Password has fewer characters than `min`
+    TooShort {
+        /// Minimum number of characters
+        min: usize,
+    },
+    /// Password is longer than `max_bytes` bytes in UTF-8
+    TooLong {
+        /// Maximum length in bytes
+        max_bytes: usize,
+    },
+    /// Password has no lowercase letter
+    MissingLowercase,
+    /// Password has no uppercase letter
+    MissingUppercase,
+    /// Password has no digit
+    MissingDigit,
+    /// Password has no symbol
+    MissingSymbol,
+    /// Password contains the username
+    ContainsUsername,
+    /// Strength score is lower than `min_score`. See [`estimate_strength`]
+    TooWeak {
+        /// Strength score of the password, 0 to 4
+        score: u8,
+        /// Minimum strength score required
+        min_score: u8,
+    },
+    /// Password appears `count` times in breach data. See [`BreachChecker`]
+    Breached {
+        /// Number of times the password appears in breach data
+        count: u64,
+    },
+}
+
+impl PolicyViolation {
+    /// Stable error code, e.g. `password_too_short`
+    pub fn code(&self) -> &'static str {
+        match self {
+            PolicyViolation::TooShort { .. } => "password_too_short",
+            PolicyViolation::TooLong { .. } => "password_too_long",
+            PolicyViolation::MissingLowercase => "password_missing_lowercase",
+            PolicyViolation::MissingUppercase => "password_missing_uppercase",
+            PolicyViolation::MissingDigit => "password_missing_digit",
+            PolicyViolation::MissingSymbol => "password_missing_symbol",
+            PolicyViolation::ContainsUsername => "password_contains_username",
+            PolicyViolation::TooWeak { .. } => "password_too_weak",
+            PolicyViolation::Breached { .. } => "password_breached",
+        }
+    }
+
+    /// Error message in the given language
+    pub fn message(&self, lang: Language) -> String {
+        use Language::*;
+
+        match (self, lang) {
+            (PolicyViolation::TooShort { min }, English) => {
+                format!("Password must be at least {} characters.", min)
+            }
+            (PolicyViolation::TooShort { min }, Indonesian) => {
+                format!("Kata sandi minimal {} karakter.", min)
+            }
+            (PolicyViolation::TooLong { max_bytes }, English) => {
+                format!("Password must not be longer than {} bytes.", max_bytes)
+            }
+            (PolicyViolation::TooLong { max_bytes }, Indonesian) => {
+                format!("Kata sandi tidak boleh lebih dari {} byte.", max_bytes)
+            }
+            (PolicyViolation::MissingLowercase, English) => {
+                "Password must contain a lowercase letter.".to_string()
+            }
+            (PolicyViolation::MissingLowercase, Indonesian) => {
+                "Kata sandi harus mengandung huruf kecil.".to_string()
+            }
+            (PolicyViolation::MissingUppercase, English) => {
+                "Password must contain an uppercase letter.".to_string()
+            }
+            (PolicyViolation::MissingUppercase, Indonesian) => {
+                "Kata sandi harus mengandung huruf besar.".to_string()
+            }
+            (PolicyViolation::MissingDigit, English) => {
+                "Password must contain a digit.".to_string()
+            }
+            (PolicyViolation::MissingDigit, Indonesian) => {
+                "Kata sandi harus mengandung angka.".to_string()
+            }
+            (PolicyViolation::MissingSymbol, English) => {
+                "Password must contain a symbol.".to_string()
+            }
+            (PolicyViolation::MissingSymbol, Indonesian) => {
+                "Kata sandi harus mengandung simbol.".to_string()
+            }
+            (PolicyViolation::ContainsUsername, English) => {
+                "Password must not contain your username.".to_string()
+            }
+            (PolicyViolation::ContainsUsername, Indonesian) => {
+                "Kata sandi tidak boleh mengandung username Anda.".to_string()
+            }
+            (PolicyViolation::TooWeak { .. }, English) => {
+                "Password is too easy to guess.".to_string()
+            }
+            (PolicyViolation::TooWeak { .. }, Indonesian) => {
+                "Kata sandi terlalu mudah ditebak.".to_string()
+            }
+            (PolicyViolation::Breached { .. }, English) => {
+                "This password has appeared in a data breach. Please choose another password."
+                    .to_string()
+            }
+            (PolicyViolation::Breached { .. }, Indonesian) => {
+                "Kata sandi ini pernah bocor dalam pelanggaran data. Silakan pilih kata sandi lain."
+                    .to_string()
+            }
+        }
+    }
+}
+
+/// Declarative password rules
+///
+/// Default: minimum 8 characters, maximum [`BCRYPT_MAX_PASSWORD_BYTES`] bytes, and must not contain the username.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{Language, PasswordPolicy};
+///
+/// let policy = PasswordPolicy::new()
+///     .min_length(10)
+///     .require_uppercase(true)
+///     .require_digit(true);
+///
+/// if let Err(violations) = policy.validate("putu1234", Some("putu")) {
+///     for violation in violations {
+///         println!("{}: {}", violation.code(), violation.message(Language::Indonesian));
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    min_length: usize,
+    max_bytes: usize,
+    require_lowercase: bool,
+    require_uppercase: bool,
+    require_digit: bool,
+    require_symbol: bool,
+    disallow_username: bool,
+    min_score: Option<u8>,
+    breach_checker: Option<Arc<BreachChecker>>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        PasswordPolicy {
+            min_length: 8,
+            max_bytes: BCRYPT_MAX_PASSWORD_BYTES,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            require_symbol: false,
+            disallow_username: true,
+            min_score: None,
+            breach_checker: None,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Create policy with default rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Minimum number of characters
+    pub fn min_length(mut self, min_length: usize) -> Self {
+        self.min_length = min_length;
+        self
+    }
+
+    /// Maximum length in UTF-8 bytes. Default: [`BCRYPT_MAX_PASSWORD_BYTES`]
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Require at least one lowercase letter
+    pub fn require_lowercase(mut self, required: bool) -> Self {
+        self.require_lowercase = required;
+        self
+    }
+
+    /// Require at least one uppercase letter
+    pub fn require_uppercase(mut self, required: bool) -> Self {
+        self.require_uppercase = required;
+        self
+    }
+
+    /// Require at least one digit
+    pub fn require_digit(mut self, required: bool) -> Self {
+        self.require_digit = required;
+        self
+    }
+
+    /// Require at least one symbol (any character that is not a letter or digit)
+    pub fn require_symbol(mut self, required: bool) -> Self {
+        self.require_symbol = required;
+        self
+    }
+
+    /// Reject passwords that contain the username (or the reversed username), case-insensitive.
+    /// Usernames shorter than 3 characters are ignored
+    pub fn disallow_username(mut self, disallow: bool) -> Self {
+        self.disallow_username = disallow;
+        self
+    }
+
+    /// Minimum [`estimate_strength`] score, from 0 to 4
+    pub fn min_score(mut self, min_score: u8) -> Self {
+        self.min_score = Some(min_score);
+        self
+    }
+
+    /// Reject passwords found in local breach data
+    ///
+    /// If the breach data can't be read, the password is not rejected.
+    pub fn breach_checker(mut self, checker: Arc<BreachChecker>) -> Self {
+        self.breach_checker = Some(checker);
+        self
+    }
+
+    /// Check the password against every rule
+    ///
+    /// Returns every violation, not only the first one.
+    pub fn validate(
+        &self,
+        password: &str,
+        username: Option<&str>,
+    ) -> Result<(), Vec<PolicyViolation>> {
+        let mut violations = Vec::new();
+
+        if password.chars().count() < self.min_length {
+            violations.push(PolicyViolation::TooShort {
+                min: self.min_length,
+            });
+        }
+        if password.len() > self.max_bytes {
+            violations.push(PolicyViolation::TooLong {
+                max_bytes: self.max_bytes,
+            });
+        }
+        if self.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
+            violations.push(PolicyViolation::MissingLowercase);
+        }
+        if self.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
+            violations.push(PolicyViolation::MissingUppercase);
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_numeric()) {
+            violations.push(PolicyViolation::MissingDigit);
+        }
+        if self.require_symbol && !password.chars().any(|c| !c.is_alphanumeric()) {
+            violations.push(PolicyViolation::MissingSymbol);
+        }
+
+        let username = username
+            .map(|u| u.trim())
+            .filter(|u| u.chars().count() >= 3);
+        if let (true, Some(username)) = (self.disallow_username, username) {
+            let password = password.to_lowercase();
+            let username = username.to_lowercase();
+            let reversed: String = username.chars().rev().collect();
+            if password.contains(&username) || password.contains(&reversed) {
+                violations.push(PolicyViolation::ContainsUsername);
+            }
+        }
+
+        if let Some(min_score) = self.min_score {
+            let user_inputs: Vec<&str> = username.into_iter().collect();
+            let score = estimate_strength(password, &user_inputs).score;
+            if score < min_score {
+                violations.push(PolicyViolation::TooWeak { score, min_score });
+            }
+        }
+
+        if let Some(checker) = &self.breach_checker {
+            if let Ok(count @ 1..) = checker.check(password) {
+                violations.push(PolicyViolation::Breached { count });
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let policy = PasswordPolicy::new()
+            .require_lowercase(true)
+            .require_uppercase(true)
+            .require_digit(true)
+            .require_symbol(true);
+
+        let cases = vec![
+            ("Kucing#Hitam99", Some("putu"), vec![]),
+            (
+                "putu",
+                Some("putu"),
+                vec![
+                    "password_too_short",
+                    "password_missing_uppercase",
+                    "password_missing_digit",
+                    "password_missing_symbol",
+                    "password_contains_username",
+                ],
+            ),
+            (
+                "Utup#12345",
+                Some("PUTU"),
+                vec!["password_contains_username"],
+            ),
+            ("Utup#12345", None, vec![]),
+            ("KUCING#HITAM99", None, vec!["password_missing_lowercase"]),
+        ];
+
+        for (password, username, expected) in cases {
+            let got: Vec<&str> = match policy.validate(password, username) {
+                Ok(()) => vec![],
+                Err(violations) => violations.iter().map(|v| v.code()).collect(),
+            };
+            assert_eq!(
+                got, expected,
+                "validate({:?}, {:?}) should be {:?}, got {:?}",
+                password, username, expected, got
+            );
+        }
+
+        // 73 bytes, but only 72 characters
+        let long = format!("Á{}#1", "a".repeat(69));
+        assert_eq!(
+            policy.validate(&long, None),
+            Err(vec![PolicyViolation::TooLong { max_bytes: 72 }])
+        );
+    }
+
+    #[test]
+    fn test_min_score() {
+        let policy = PasswordPolicy::new().min_score(3);
+
+        let violations = policy
+            .validate("password123", None)
+            .expect_err("weak password should be rejected");
+        assert!(matches!(
+            violations[..],
+            [PolicyViolation::TooWeak { min_score: 3, .. }]
+        ));
+        assert!(policy.validate("kucing-hitam-makan-durian", None).is_ok());
+    }
+
+    #[test]
+    fn test_breach_checker() {
+        let data = "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8:3861493";
+        let checker = BreachChecker::from_bytes(data.as_bytes().to_vec());
+        let policy = PasswordPolicy::new().breach_checker(Arc::new(checker));
+
+        assert_eq!(
+            policy.validate("password", None),
+            Err(vec![PolicyViolation::Breached { count: 3861493 }])
+        );
+        assert!(policy.validate("kucing-hitam", None).is_ok());
+    }
+
+    #[test]
+    fn test_message() {
+        let violation = PolicyViolation::TooShort { min: 8 };
+        assert_eq!(
+            violation.message(Language::English),
+            "Password must be at least 8 characters."
+        );
+        assert_eq!(
+            violation.message(Language::Indonesian),
+            "Kata sandi minimal 8 karakter."
+        );
+    }
+}
@@ -0,0 +1,1474 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use super::Language;
+use lazy_static::lazy_static;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Only the first 100 characters are analyzed
+const MAX_PASSWORD_LENGTH: usize = 100;
+const MAX_WORD_LENGTH: usize = 30;
+const MIN_WORD_LENGTH: usize = 3;
+const BRUTEFORCE_CARDINALITY: f64 = 10.0;
+const MIN_GUESSES_BEFORE_GROWING_SEQUENCE: f64 = 10_000.0;
+const MIN_SUBMATCH_GUESSES_SINGLE_CHAR: f64 = 10.0;
+const MIN_SUBMATCH_GUESSES_MULTI_CHAR: f64 = 50.0;
+const MIN_YEAR_SPACE: f64 = 20.0;
+const DATE_SEPARATORS: [char; 6] = [' ', '-', '/', '\\', '_', '.'];
+
+/// Embedded dictionary used by the strength estimator
+///
+/// The embedded lists are small, a few hundred entries each, compared to the ~30k entries per
+/// list in zxcvbn. Common words and passwords that are not listed are scored as brute force, so
+/// see the limitation in [`estimate_strength`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dictionary {
+    /// Common passwords, including the ones popular in Indonesia
+    Passwords,
+    /// Common English words
+    English,
+    /// Common Indonesian words
+    Indonesian,
+    /// Common Indonesian (and English) first names
+    Names,
+    /// User inputs such as name and email
+    UserInputs,
+}
+
+/// Pattern detected in a password
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// Dictionary word, optionally reversed or with l33t substitutions
+    Dictionary {
+        /// Dictionary that contains the word
+        dictionary: Dictionary,
+        /// Word from the dictionary, in lowercase
+        matched_word: String,
+        /// Frequency rank of the word, 1 is the most common
+        rank: usize,
+        /// The word is written backwards
+        reversed: bool,
+        /// The word uses l33t substitutions, e.g. `p@ssw0rd`
+        l33t: bool,
+    },
+    /// Keyboard pattern on a QWERTY layout, e.g. `qwerty` or `zxcvfr`
+    Spatial {
+        /// Number of direction changes
+        turns: usize,
+        /// Number of characters typed with shift
+        shifted_count: usize,
+    },
+    /// Character sequence, e.g. `abcd` or `9753`
+    Sequence {
+        /// The sequence goes up, e.g. `abc` instead of `cba`
+        ascending: bool,
+    },
+    /// Repeated characters or patterns, e.g. `aaa` or `abcabc`
+    Repeat {
+        /// Repeated part, e.g. `abc` for `abcabc`
+        base_token: String,
+        /// Number of times the base is repeated
+        repeat_count: usize,
+    },
+    /// Date, e.g. `17081945` or `17-08-1945`
+    Date {
+        /// Year, two-digit years are expanded
+        year: i32,
+        /// Month, 1 to 12
+        month: u32,
+        /// Day of the month
+        day: u32,
+        /// Separator between the parts, `None` if the digits are joined
+        separator: Option<char>,
+    },
+    /// Year between 1900 and 2099
+    Year {
+        /// Matched year
+        year: i32,
+    },
+    /// Characters that don't match any pattern
+    BruteForce,
+}
+
+/// Part of the password matched by a [`Pattern`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternMatch {
+    /// Index of the first character
+    pub i: usize,
+    /// Index of the last character (inclusive)
+    pub j: usize,
+    /// Matched part of the password
+    pub token: String,
+    /// Pattern that matches the token
+    pub pattern: Pattern,
+    /// Estimated guesses needed to find this part
+    pub guesses: f64,
+}
+
+/// Estimated time in seconds to crack the password
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrackTimes {
+    /// Online attack with rate limiting (100 guesses per hour)
+    pub online_throttling: f64,
+    /// Online attack without rate limiting (10 guesses per second)
+    pub online_no_throttling: f64,
+    /// Offline attack against a slow hash such as bcrypt (10k guesses per second)
+    pub offline_slow_hashing: f64,
+    /// Offline attack against a fast hash such as SHA-1 (10B guesses per second)
+    pub offline_fast_hashing: f64,
+}
+
+impl CrackTimes {
+    fn from_guesses(guesses: f64) -> Self {
+        CrackTimes {
+            online_throttling: guesses / (100.0 / 3600.0),
+            online_no_throttling: guesses / 10.0,
+            offline_slow_hashing: guesses / 1e4,
+            offline_fast_hashing: guesses / 1e10,
+        }
+    }
+}
+
+/// Warning about the weakest part of the password
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Warning {
+    /// Straight row of keys, e.g. `qwerty`
+    StraightRowsOfKeys,
+    /// Short keyboard pattern with turns
+    ShortKeyboardPatterns,
+    /// Repeated single character, e.g. `aaa`
+    RepeatedCharacters,
+    /// Repeated pattern, e.g. `abcabc`
+    RepeatedPatterns,
+    /// Character sequence, e.g. `abc` or `6543`
+    Sequences,
+    /// Recent year
+    RecentYears,
+    /// Date
+    Dates,
+    /// One of the 10 most common passwords
+    TopTenPassword,
+    /// One of the 100 most common passwords
+    TopHundredPassword,
+    /// Common password
+    CommonPassword,
+    /// Common password with l33t substitutions or reversed
+    SimilarToCommonPassword,
+    /// A single dictionary word
+    WordByItself,
+    /// A single name
+    NamesByThemselves,
+    /// Common name combined with other parts
+    CommonNames,
+    /// Contains the user's name, username or email
+    UserInputs,
+}
+
+impl Warning {
+    /// Warning message in the given language
+    pub fn message(&self, lang: Language) -> &'static str {
+        use Language::*;
+        use Warning::*;
+
+        match (self, lang) {
+            (StraightRowsOfKeys, English) => "Straight rows of keys are easy to guess.",
+            (StraightRowsOfKeys, Indonesian) => "Deretan tombol keyboard mudah ditebak.",
+            (ShortKeyboardPatterns, English) => "Short keyboard patterns are easy to guess.",
+            (ShortKeyboardPatterns, Indonesian) => "Pola keyboard yang pendek mudah ditebak.",
+            (RepeatedCharacters, English) => "Repeats like \"aaa\" are easy to guess.",
+            (RepeatedCharacters, Indonesian) => "Pengulangan seperti \"aaa\" mudah ditebak.",
+            (RepeatedPatterns, English) => {
+                "Repeats like \"abcabcabc\" are only slightly harder to guess than \"abc\"."
+            }
+            (RepeatedPatterns, Indonesian) => {
+                "Pengulangan seperti \"abcabcabc\" hanya sedikit lebih sulit ditebak daripada \"abc\"."
+            }
+            (Sequences, English) => "Sequences like \"abc\" or \"6543\" are easy to guess.",
+            (Sequences, Indonesian) => "Urutan seperti \"abc\" atau \"6543\" mudah ditebak.",
+            (RecentYears, English) => "Recent years are easy to guess.",
+            (RecentYears, Indonesian) => "Tahun-tahun terakhir mudah ditebak.",
+            (Dates, English) => "Dates are often easy to guess.",
+            (Dates, Indonesian) => "Tanggal biasanya mudah ditebak.",
+            (TopTenPassword, English) => "This is a top-10 common password.",
+            (TopTenPassword, Indonesian) => "Ini adalah salah satu dari 10 kata sandi paling umum.",
+            (TopHundredPassword, English) => "This is a top-100 common password.",
+            (TopHundredPassword, Indonesian) => {
+                "Ini adalah salah satu dari 100 kata sandi paling umum."
+            }
+            (CommonPassword, English) => "This is a very common password.",
+            (CommonPassword, Indonesian) => "Ini adalah kata sandi yang sangat umum.",
+            (SimilarToCommonPassword, English) => "This is similar to a commonly used password.",
+            (SimilarToCommonPassword, Indonesian) => {
+                "Ini mirip dengan kata sandi yang sering digunakan."
+            }
+            (WordByItself, English) => "A word by itself is easy to guess.",
+            (WordByItself, Indonesian) => "Satu kata saja mudah ditebak.",
+            (NamesByThemselves, English) => "Names and surnames by themselves are easy to guess.",
+            (NamesByThemselves, Indonesian) => "Nama depan atau nama belakang saja mudah ditebak.",
+            (CommonNames, English) => "Common names and surnames are easy to guess.",
+            (CommonNames, Indonesian) => "Nama yang umum mudah ditebak.",
+            (UserInputs, English) => "Avoid using your name, username or email.",
+            (UserInputs, Indonesian) => "Hindari menggunakan nama, username atau email Anda.",
+        }
+    }
+}
+
+/// Suggestion to make the password stronger
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suggestion {
+    /// Use a few uncommon words
+    UseFewWords,
+    /// Length matters more than symbols, digits, or uppercase letters
+    NoNeedForSymbols,
+    /// Add another word
+    AddAnotherWord,
+    /// Use a longer keyboard pattern with more turns
+    LongerKeyboardPattern,
+    /// Avoid repeated words and characters
+    AvoidRepeats,
+    /// Avoid sequences
+    AvoidSequences,
+    /// Avoid recent years
+    AvoidRecentYears,
+    /// Avoid dates
+    AvoidDates,
+    /// Capitalizing the first letter does not help
+    Capitalization,
+    /// All-uppercase does not help
+    AllUppercase,
+    /// Reversed words do not help
+    ReversedWords,
+    /// Predictable l33t substitutions do not help
+    L33t,
+}
+
+impl Suggestion {
+    /// Suggestion message in the given language
+    pub fn message(&self, lang: Language) -> &'static str {
+        use Language::*;
+        use Suggestion::*;
+
+        match (self, lang) {
+            (UseFewWords, English) => "Use a few words, avoid common phrases.",
+            (UseFewWords, Indonesian) => "Gunakan beberapa kata, hindari frasa yang umum.",
+            (NoNeedForSymbols, English) => "No need for symbols, digits, or uppercase letters.",
+            (NoNeedForSymbols, Indonesian) => "Tidak perlu simbol, angka, atau huruf besar.",
+            (AddAnotherWord, English) => "Add another word or two. Uncommon words are better.",
+            (AddAnotherWord, Indonesian) => {
+                "Tambahkan satu atau dua kata lagi. Kata yang tidak umum lebih baik."
+            }
+            (LongerKeyboardPattern, English) => "Use a longer keyboard pattern with more turns.",
+            (LongerKeyboardPattern, Indonesian) => {
+                "Gunakan pola keyboard yang lebih panjang dengan lebih banyak belokan."
+            }
+            (AvoidRepeats, English) => "Avoid repeated words and characters.",
+            (AvoidRepeats, Indonesian) => "Hindari kata dan karakter yang berulang.",
+            (AvoidSequences, English) => "Avoid sequences.",
+            (AvoidSequences, Indonesian) => "Hindari urutan karakter.",
+            (AvoidRecentYears, English) => "Avoid recent years and years associated with you.",
+            (AvoidRecentYears, Indonesian) => {
+                "Hindari tahun-tahun terakhir dan tahun yang berhubungan dengan Anda."
+            }
+            (AvoidDates, English) => "Avoid dates and years associated with you.",
+            (AvoidDates, Indonesian) => "Hindari tanggal dan tahun yang berhubungan dengan Anda.",
+            (Capitalization, English) => "Capitalization doesn't help very much.",
+            (Capitalization, Indonesian) => "Huruf kapital di awal tidak banyak membantu.",
+            (AllUppercase, English) => "All-uppercase is almost as easy to guess as all-lowercase.",
+            (AllUppercase, Indonesian) => {
+                "Semua huruf besar hampir sama mudahnya ditebak dengan semua huruf kecil."
+            }
+            (ReversedWords, English) => "Reversed words aren't much harder to guess.",
+            (ReversedWords, Indonesian) => "Kata yang dibalik tidak jauh lebih sulit ditebak.",
+            (L33t, English) => {
+                "Predictable substitutions like \"@\" instead of \"a\" don't help very much."
+            }
+            (L33t, Indonesian) => {
+                "Substitusi yang mudah ditebak seperti \"@\" untuk \"a\" tidak banyak membantu."
+            }
+        }
+    }
+}
+
+/// Feedback for weak passwords
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Feedback {
+    /// Problem with the weakest part of the password, if any
+    pub warning: Option<Warning>,
+    /// Ways to make the password stronger
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Feedback {
+    /// Warning message in the given language
+    pub fn warning_message(&self, lang: Language) -> Option<&'static str> {
+        self.warning.map(|w| w.message(lang))
+    }
+
+    /// Suggestion messages in the given language
+    pub fn suggestion_messages(&self, lang: Language) -> Vec<&'static str> {
+        self.suggestions.iter().map(|s| s.message(lang)).collect()
+    }
+}
+
+/// Result of [`estimate_strength`]
+///
+/// The estimate is only as good as the embedded dictionaries, which are small. Treat the score
+/// as an upper bound, see [`estimate_strength`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasswordStrength {
+    /// Estimated guesses needed to crack the password
+    pub guesses: f64,
+    /// Base-10 logarithm of `guesses`
+    pub guesses_log10: f64,
+    /// Score from 0 (too guessable) to 4 (very unguessable)
+    ///
+    /// May be too high for common words or passwords that are not in the embedded dictionaries.
+    pub score: u8,
+    /// Estimated time to crack the password in several attack scenarios
+    pub crack_times: CrackTimes,
+    /// Warning and suggestions, only given for scores below 3
+    pub feedback: Feedback,
+    /// Patterns that make up the password
+    pub sequence: Vec<PatternMatch>,
+}
+
+lazy_static! {
+    static ref DICTIONARIES: Vec<(Dictionary, HashMap<String, usize>)> = vec![
+        (
+            Dictionary::Passwords,
+            ranked(include_str!("data/passwords.txt"))
+        ),
+        (
+            Dictionary::English,
+            ranked(include_str!("data/english.txt"))
+        ),
+        (
+            Dictionary::Indonesian,
+            ranked(include_str!("data/indonesian.txt"))
+        ),
+        (Dictionary::Names, ranked(include_str!("data/names.txt"))),
+    ];
+    static ref KEYBOARD: HashMap<char, (i32, i32, bool)> = qwerty();
+    static ref KEYBOARD_AVERAGE_DEGREE: f64 = average_degree();
+}
+
+/// Estimate password strength, zxcvbn style
+///
+/// The password is checked against embedded English and Indonesian dictionaries, keyboard patterns,
+/// sequences, repeats and dates. `user_inputs` such as name, username and email are penalized too.
+///
+/// ## Limitation
+///
+/// The embedded dictionaries only hold a few hundred of the most common passwords, words, and
+/// names per list, while zxcvbn ships about 30k entries per list. A common word or password that
+/// is not listed is estimated as brute force, so its score can be too high. Do not rely on the
+/// score alone: combine it with a minimum length (see [`PasswordPolicy`](super::PasswordPolicy))
+/// and a breached password check (see [`BreachChecker`](super::BreachChecker)).
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{estimate_strength, Language};
+///
+/// let strength = estimate_strength("putu1995", &["Putu Aditya", "aditya@portalnesia.com"]);
+/// assert!(strength.score < 2);
+///
+/// if let Some(warning) = strength.feedback.warning_message(Language::Indonesian) {
+///     println!("{}", warning);
+/// }
+/// ```
+pub fn estimate_strength(password: &str, user_inputs: &[&str]) -> PasswordStrength {
+    let chars: Vec<char> = password.chars().take(MAX_PASSWORD_LENGTH).collect();
+    let user_inputs = user_input_dictionary(user_inputs);
+
+    let matches = omnimatch(&chars, &user_inputs);
+    let (guesses, sequence) = most_guessable(&chars, matches);
+    let score = score(guesses);
+
+    PasswordStrength {
+        guesses,
+        guesses_log10: guesses.log10(),
+        score,
+        crack_times: CrackTimes::from_guesses(guesses),
+        feedback: feedback(score, &sequence),
+        sequence,
+    }
+}
+
+/// Format crack time in seconds to human-readable string
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::password::{display_time, Language};
+///
+/// assert_eq!(display_time(7200.0, Language::English), "2 hours");
+/// assert_eq!(display_time(7200.0, Language::Indonesian), "2 jam");
+/// ```
+pub fn display_time(seconds: f64, lang: Language) -> String {
+    const MINUTE: f64 = 60.0;
+    const HOUR: f64 = MINUTE * 60.0;
+    const DAY: f64 = HOUR * 24.0;
+    const MONTH: f64 = DAY * 31.0;
+    const YEAR: f64 = MONTH * 12.0;
+    const CENTURY: f64 = YEAR * 100.0;
+
+    let (value, unit) = if seconds < 1.0 {
+        return match lang {
+            Language::English => "less than a second".to_string(),
+            Language::Indonesian => "kurang dari satu detik".to_string(),
+        };
+    } else if seconds < MINUTE {
+        (seconds, ("second", "detik"))
+    } else if seconds < HOUR {
+        (seconds / MINUTE, ("minute", "menit"))
+    } else if seconds < DAY {
+        (seconds / HOUR, ("hour", "jam"))
+    } else if seconds < MONTH {
+        (seconds / DAY, ("day", "hari"))
+    } else if seconds < YEAR {
+        (seconds / MONTH, ("month", "bulan"))
+    } else if seconds < CENTURY {
+        (seconds / YEAR, ("year", "tahun"))
+    } else {
+        return match lang {
+            Language::English => "centuries".to_string(),
+            Language::Indonesian => "berabad-abad".to_string(),
+        };
+    };
+
+    let value = value.round() as u64;
+    match lang {
+        Language::English if value == 1 => format!("{} {}", value, unit.0),
+        Language::English => format!("{} {}s", value, unit.0),
+        Language::Indonesian => format!("{} {}", value, unit.1),
+    }
+}
+
+fn ranked(list: &str) -> HashMap<String, usize> {
+    let mut map = HashMap::new();
+    for word in list.lines().map(|w| w.trim().to_lowercase()) {
+        if word.is_empty() {
+            continue;
+        }
+        let rank = map.len() + 1;
+        map.entry(word).or_insert(rank);
+    }
+    map
+}
+
+/// Build dictionary from user inputs. Email and full name are also split into parts
+fn user_input_dictionary(user_inputs: &[&str]) -> HashMap<String, usize> {
+    let mut map = HashMap::new();
+    for input in user_inputs {
+        let input = input.trim().to_lowercase();
+        let parts = input
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|p| p.chars().count() >= MIN_WORD_LENGTH)
+            .map(|p| p.to_string());
+
+        for word in std::iter::once(input.clone()).chain(parts) {
+            if word.is_empty() {
+                continue;
+            }
+            let rank = map.len() + 1;
+            map.entry(word).or_insert(rank);
+        }
+    }
+    map
+}
+
+fn omnimatch(chars: &[char], user_inputs: &HashMap<String, usize>) -> Vec<PatternMatch> {
+    let mut matches = Vec::new();
+    matches.extend(dictionary_match(chars, user_inputs));
+    matches.extend(reverse_dictionary_match(chars, user_inputs));
+    matches.extend(l33t_match(chars, user_inputs));
+    matches.extend(spatial_match(chars));
+    matches.extend(sequence_match(chars));
+    matches.extend(repeat_match(chars, user_inputs));
+    matches.extend(date_match(chars, reference_year()));
+    matches
+}
+
+fn token(chars: &[char], i: usize, j: usize) -> String {
+    chars[i..=j].iter().collect()
+}
+
+/// Find dictionary words in lowercased password, returns (i, j, dictionary, word, rank)
+fn find_words(
+    lower: &[char],
+    user_inputs: &HashMap<String, usize>,
+) -> Vec<(usize, usize, Dictionary, String, usize)> {
+    let dictionaries = DICTIONARIES
+        .iter()
+        .map(|(d, words)| (*d, words))
+        .chain(std::iter::once((Dictionary::UserInputs, user_inputs)));
+
+    let mut found = Vec::new();
+    for (dictionary, words) in dictionaries {
+        for i in 0..lower.len() {
+            let max_j = lower.len().min(i + MAX_WORD_LENGTH);
+            for j in (i + MIN_WORD_LENGTH - 1)..max_j {
+                let word = token(lower, i, j);
+                if let Some(&rank) = words.get(&word) {
+                    found.push((i, j, dictionary, word, rank));
+                }
+            }
+        }
+    }
+    found
+}
+
+fn lowercase(chars: &[char]) -> Vec<char> {
+    chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect()
+}
+
+fn dictionary_match(chars: &[char], user_inputs: &HashMap<String, usize>) -> Vec<PatternMatch> {
+    let lower = lowercase(chars);
+
+    find_words(&lower, user_inputs)
+        .into_iter()
+        .map(|(i, j, dictionary, word, rank)| {
+            let token = token(chars, i, j);
+            let guesses = rank as f64 * uppercase_variations(&token);
+            PatternMatch {
+                i,
+                j,
+                token,
+                pattern: Pattern::Dictionary {
+                    dictionary,
+                    matched_word: word,
+                    rank,
+                    reversed: false,
+                    l33t: false,
+                },
+                guesses,
+            }
+        })
+        .collect()
+}
+
+fn reverse_dictionary_match(
+    chars: &[char],
+    user_inputs: &HashMap<String, usize>,
+) -> Vec<PatternMatch> {
+    let n = chars.len();
+    let reversed: Vec<char> = lowercase(chars).into_iter().rev().collect();
+
+    find_words(&reversed, user_inputs)
+        .into_iter()
+        .filter_map(|(i, j, dictionary, word, rank)| {
+            // Palindrome sudah ditemukan oleh dictionary_match
+            if word.chars().rev().collect::<String>() == word {
+                return None;
+            }
+            let (i, j) = (n - 1 - j, n - 1 - i);
+            let token = token(chars, i, j);
+            let guesses = rank as f64 * uppercase_variations(&token) * 2.0;
+            Some(PatternMatch {
+                i,
+                j,
+                token,
+                pattern: Pattern::Dictionary {
+                    dictionary,
+                    matched_word: word,
+                    rank,
+                    reversed: true,
+                    l33t: false,
+                },
+                guesses,
+            })
+        })
+        .collect()
+}
+
+/// l33t substitutions, the first letter is the primary one
+const L33T_TABLE: [(char, &[char]); 15] = [
+    ('4', &['a']),
+    ('@', &['a']),
+    ('8', &['b']),
+    ('(', &['c']),
+    ('3', &['e']),
+    ('6', &['g']),
+    ('9', &['g']),
+    ('1', &['i', 'l']),
+    ('!', &['i']),
+    ('|', &['i', 'l']),
+    ('7', &['t', 'l']),
+    ('0', &['o']),
+    ('$', &['s']),
+    ('5', &['s']),
+    ('+', &['t']),
+];
+
+fn l33t_match(chars: &[char], user_inputs: &HashMap<String, usize>) -> Vec<PatternMatch> {
+    let lower = lowercase(chars);
+    if !lower.iter().any(|c| L33T_TABLE.iter().any(|(s, _)| s == c)) {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<PatternMatch> = Vec::new();
+    // Varian 0 memakai huruf utama, varian 1 memakai huruf alternatif (contoh: 1 => l)
+    for variant in 0..2 {
+        let translated: Vec<char> = lower
+            .iter()
+            .map(|c| match L33T_TABLE.iter().find(|(s, _)| s == c) {
+                Some((_, letters)) => letters[variant.min(letters.len() - 1)],
+                None => *c,
+            })
+            .collect();
+
+        for (i, j, dictionary, word, rank) in find_words(&translated, user_inputs) {
+            if lower[i..=j] == translated[i..=j] {
+                continue;
+            }
+            let token = token(chars, i, j);
+            let variations = l33t_variations(&lower[i..=j], &translated[i..=j]);
+            let guesses = rank as f64 * uppercase_variations(&token) * variations;
+
+            let duplicate = matches
+                .iter()
+                .any(|m| m.i == i && m.j == j && m.guesses <= guesses);
+            if duplicate {
+                continue;
+            }
+            matches.push(PatternMatch {
+                i,
+                j,
+                token,
+                pattern: Pattern::Dictionary {
+                    dictionary,
+                    matched_word: word,
+                    rank,
+                    reversed: false,
+                    l33t: true,
+                },
+                guesses,
+            });
+        }
+    }
+    matches
+}
+
+fn uppercase_variations(token: &str) -> f64 {
+    let chars: Vec<char> = token.chars().collect();
+    let upper = chars.iter().filter(|c| c.is_uppercase()).count();
+    let lower = chars.iter().filter(|c| c.is_lowercase()).count();
+
+    if upper == 0 {
+        return 1.0;
+    }
+    let first_upper = chars.first().is_some_and(|c| c.is_uppercase());
+    let last_upper = chars.last().is_some_and(|c| c.is_uppercase());
+    if lower == 0 || (upper == 1 && (first_upper || last_upper)) {
+        return 2.0;
+    }
+
+    (1..=upper.min(lower)).map(|i| n_ck(upper + lower, i)).sum()
+}
+
+fn l33t_variations(lower: &[char], translated: &[char]) -> f64 {
+    let mut subs: Vec<(char, char)> = Vec::new();
+    for (l, t) in lower.iter().zip(translated) {
+        if l != t && !subs.contains(&(*l, *t)) {
+            subs.push((*l, *t));
+        }
+    }
+
+    let mut variations = 1.0;
+    for (subbed, letter) in subs {
+        let s = lower.iter().filter(|c| **c == subbed).count();
+        let u = lower.iter().filter(|c| **c == letter).count();
+        if s == 0 || u == 0 {
+            variations *= 2.0;
+        } else {
+            variations *= (1..=s.min(u)).map(|i| n_ck(s + u, i)).sum::<f64>();
+        }
+    }
+    variations
+}
+
+fn qwerty() -> HashMap<char, (i32, i32, bool)> {
+    const ROWS: [(&str, &str); 4] = [
+        ("`1234567890-=", "~!@#$%^&*()_+"),
+        ("qwertyuiop[]\\", "QWERTYUIOP{}|"),
+        ("asdfghjkl;'", "ASDFGHJKL:\""),
+        ("zxcvbnm,./", "ZXCVBNM<>?"),
+    ];
+
+    let mut map = HashMap::new();
+    for (y, (lower, upper)) in ROWS.iter().enumerate() {
+        for (x, (l, u)) in lower.chars().zip(upper.chars()).enumerate() {
+            map.insert(l, (x as i32, y as i32, false));
+            map.insert(u, (x as i32, y as i32, true));
+        }
+    }
+    map
+}
+
+/// Neighbour directions on a slanted keyboard
+const DIRECTIONS: [(i32, i32); 6] = [(-1, 0), (1, 0), (0, -1), (1, -1), (-1, 1), (0, 1)];
+
+fn direction(a: char, b: char) -> Option<usize> {
+    let (ax, ay, _) = KEYBOARD.get(&a)?;
+    let (bx, by, _) = KEYBOARD.get(&b)?;
+    DIRECTIONS.iter().position(|&d| d == (bx - ax, by - ay))
+}
+
+fn average_degree() -> f64 {
+    let positions: Vec<(i32, i32)> = KEYBOARD
+        .values()
+        .filter(|(_, _, shifted)| !shifted)
+        .map(|(x, y, _)| (*x, *y))
+        .collect();
+
+    let degrees: usize = positions
+        .iter()
+        .map(|(x, y)| {
+            DIRECTIONS
+                .iter()
+                .filter(|(dx, dy)| positions.contains(&(x + dx, y + dy)))
+                .count()
+        })
+        .sum();
+    degrees as f64 / positions.len() as f64
+}
+
+fn spatial_match(chars: &[char]) -> Vec<PatternMatch> {
+    let is_shifted = |c: &char| KEYBOARD.get(c).is_some_and(|(_, _, shifted)| *shifted);
+
+    let mut matches = Vec::new();
+    let mut i = 0;
+    while i + 1 < chars.len() {
+        let mut j = i;
+        let mut last_direction = None;
+        let mut turns = 0;
+        let mut shifted_count = usize::from(is_shifted(&chars[i]));
+
+        while j + 1 < chars.len() {
+            match direction(chars[j], chars[j + 1]) {
+                Some(d) => {
+                    if last_direction != Some(d) {
+                        turns += 1;
+                        last_direction = Some(d);
+                    }
+                    if is_shifted(&chars[j + 1]) {
+                        shifted_count += 1;
+                    }
+                    j += 1;
+                }
+                None => break,
+            }
+        }
+
+        if j - i + 1 >= 3 {
+            let token = token(chars, i, j);
+            let guesses = spatial_guesses(j - i + 1, turns, shifted_count);
+            matches.push(PatternMatch {
+                i,
+                j,
+                token,
+                pattern: Pattern::Spatial {
+                    turns,
+                    shifted_count,
+                },
+                guesses,
+            });
+        }
+        i = j.max(i + 1);
+    }
+    matches
+}
+
+fn spatial_guesses(length: usize, turns: usize, shifted_count: usize) -> f64 {
+    let starting_positions = KEYBOARD.len() as f64;
+    let degree = *KEYBOARD_AVERAGE_DEGREE;
+
+    let mut guesses = 0.0;
+    for i in 2..=length {
+        for j in 1..=turns.min(i - 1) {
+            guesses += n_ck(i - 1, j - 1) * starting_positions * degree.powi(j as i32);
+        }
+    }
+
+    if shifted_count > 0 {
+        let unshifted = length - shifted_count;
+        if unshifted == 0 {
+            guesses *= 2.0;
+        } else {
+            guesses *= (1..=shifted_count.min(unshifted))
+                .map(|i| n_ck(shifted_count + unshifted, i))
+                .sum::<f64>();
+        }
+    }
+    guesses
+}
+
+fn sequence_match(chars: &[char]) -> Vec<PatternMatch> {
+    const MAX_DELTA: i64 = 5;
+
+    let mut matches = Vec::new();
+    if chars.len() < 3 {
+        return matches;
+    }
+
+    let deltas: Vec<i64> = chars
+        .windows(2)
+        .map(|w| w[1] as i64 - w[0] as i64)
+        .collect();
+
+    let mut i = 0;
+    while i < deltas.len() {
+        let delta = deltas[i];
+        let mut j = i;
+        while j + 1 < deltas.len() && deltas[j + 1] == delta {
+            j += 1;
+        }
+
+        // Karakter i sampai j + 1
+        let length = j - i + 2;
+        if length >= 3 && delta != 0 && delta.abs() <= MAX_DELTA {
+            let first = chars[i];
+            let mut base = if "aAzZ019".contains(first) {
+                4.0
+            } else if first.is_ascii_digit() {
+                10.0
+            } else {
+                26.0
+            };
+            if delta < 0 {
+                base *= 2.0;
+            }
+
+            matches.push(PatternMatch {
+                i,
+                j: j + 1,
+                token: token(chars, i, j + 1),
+                pattern: Pattern::Sequence {
+                    ascending: delta > 0,
+                },
+                guesses: base * length as f64,
+            });
+        }
+        i = j + 1;
+    }
+    matches
+}
+
+fn repeat_match(chars: &[char], user_inputs: &HashMap<String, usize>) -> Vec<PatternMatch> {
+    let n = chars.len();
+    let mut matches = Vec::new();
+
+    let mut i = 0;
+    while i < n {
+        // (panjang unit, jumlah pengulangan) dengan cakupan terpanjang
+        let mut best: Option<(usize, usize)> = None;
+        for unit in 1..=(n - i) / 2 {
+            let mut count = 1;
+            while i + (count + 1) * unit <= n
+                && chars[i..i + unit] == chars[i + count * unit..i + (count + 1) * unit]
+            {
+                count += 1;
+            }
+            let covered = unit * count;
+            if count >= 2 && covered >= 3 && best.is_none_or(|(u, c)| covered > u * c) {
+                best = Some((unit, count));
+            }
+        }
+
+        match best {
+            Some((unit, repeat_count)) => {
+                let j = i + unit * repeat_count - 1;
+                let base = &chars[i..i + unit];
+                let (base_guesses, _) = most_guessable(base, omnimatch(base, user_inputs));
+
+                matches.push(PatternMatch {
+                    i,
+                    j,
+                    token: token(chars, i, j),
+                    pattern: Pattern::Repeat {
+                        base_token: base.iter().collect(),
+                        repeat_count,
+                    },
+                    guesses: base_guesses * repeat_count as f64,
+                });
+                i = j + 1;
+            }
+            None => i += 1,
+        }
+    }
+    matches
+}
+
+fn reference_year() -> i32 {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    1970 + (seconds / 31_556_952) as i32
+}
+
+fn date_match(chars: &[char], reference_year: i32) -> Vec<PatternMatch> {
+    let n = chars.len();
+    let mut matches = Vec::new();
+
+    for i in 0..n {
+        // Tahun dan tanggal tanpa separator, contoh: 1945, 17081945
+        for length in 4..=8 {
+            if i + length > n {
+                break;
+            }
+            let digits = &chars[i..i + length];
+            if !digits.iter().all(|c| c.is_ascii_digit()) {
+                break;
+            }
+            let digits: String = digits.iter().collect();
+            let j = i + length - 1;
+
+            if length == 4 {
+                let year: i32 = digits.parse().unwrap_or(0);
+                if (1900..=2099).contains(&year) {
+                    matches.push(PatternMatch {
+                        i,
+                        j,
+                        token: digits.clone(),
+                        pattern: Pattern::Year { year },
+                        guesses: year_space(year, reference_year),
+                    });
+                }
+            }
+
+            let mut best: Option<(i32, u32, u32)> = None;
+            for p1 in 1..length - 1 {
+                for p2 in p1 + 1..length {
+                    let parts = [&digits[..p1], &digits[p1..p2], &digits[p2..]];
+                    if let Some(date) = parse_date(parts, reference_year) {
+                        best = closest_date(best, date, reference_year);
+                    }
+                }
+            }
+            if let Some((year, month, day)) = best {
+                matches.push(date_pattern(
+                    i,
+                    j,
+                    digits,
+                    (year, month, day),
+                    None,
+                    reference_year,
+                ));
+            }
+        }
+
+        // Tanggal dengan separator, contoh: 17-08-1945, 1945/8/17
+        for length in 6..=10 {
+            if i + length > n {
+                break;
+            }
+            let token: String = chars[i..i + length].iter().collect();
+            let separators: Vec<char> = token.chars().filter(|c| !c.is_ascii_digit()).collect();
+            if separators.len() != 2
+                || separators[0] != separators[1]
+                || !DATE_SEPARATORS.contains(&separators[0])
+            {
+                continue;
+            }
+
+            let parts: Vec<&str> = token.split(separators[0]).collect();
+            if parts.len() != 3 || parts.iter().any(|p| p.is_empty() || p.len() > 4) {
+                continue;
+            }
+            if let Some(date) = parse_date([parts[0], parts[1], parts[2]], reference_year) {
+                let j = i + length - 1;
+                matches.push(date_pattern(
+                    i,
+                    j,
+                    token,
+                    date,
+                    Some(separators[0]),
+                    reference_year,
+                ));
+            }
+        }
+    }
+    matches
+}
+
+/// Parse three digit groups as day-month-year, month-day-year or year-month-day
+fn parse_date(parts: [&str; 3], reference_year: i32) -> Option<(i32, u32, u32)> {
+    let to_year = |s: &str| -> Option<i32> {
+        let value: i32 = s.parse().ok()?;
+        match s.len() {
+            2 if value > 50 => Some(1900 + value),
+            2 => Some(2000 + value),
+            4 if (1000..=2050).contains(&value) => Some(value),
+            _ => None,
+        }
+    };
+    let to_day_month = |s: &str| -> Option<u32> {
+        if s.len() > 2 {
+            return None;
+        }
+        s.parse().ok()
+    };
+    let valid = |month: u32, day: u32| (1..=12).contains(&month) && (1..=31).contains(&day);
+
+    let mut candidates = Vec::new();
+    if let (Some(year), Some(a), Some(b)) = (
+        to_year(parts[2]),
+        to_day_month(parts[0]),
+        to_day_month(parts[1]),
+    ) {
+        if valid(b, a) {
+            candidates.push((year, b, a));
+        }
+        if valid(a, b) {
+            candidates.push((year, a, b));
+        }
+    }
+    if let (Some(year), Some(month), Some(day)) = (
+        to_year(parts[0]),
+        to_day_month(parts[1]),
+        to_day_month(parts[2]),
+    ) {
+        if valid(month, day) {
+            candidates.push((year, month, day));
+        }
+    }
+
+    candidates
+        .into_iter()
+        .fold(None, |best, date| closest_date(best, date, reference_year))
+}
+
+fn closest_date(
+    best: Option<(i32, u32, u32)>,
+    date: (i32, u32, u32),
+    reference_year: i32,
+) -> Option<(i32, u32, u32)> {
+    match best {
+        Some(b) if (b.0 - reference_year).abs() <= (date.0 - reference_year).abs() => Some(b),
+        _ => Some(date),
+    }
+}
+
+fn year_space(year: i32, reference_year: i32) -> f64 {
+    ((year - reference_year).abs() as f64).max(MIN_YEAR_SPACE)
+}
+
+fn date_pattern(
+    i: usize,
+    j: usize,
+    token: String,
+    (year, month, day): (i32, u32, u32),
+    separator: Option<char>,
+    reference_year: i32,
+) -> PatternMatch {
+    let mut guesses = year_space(year, reference_year) * 365.0;
+    if separator.is_some() {
+        guesses *= 4.0;
+    }
+    PatternMatch {
+        i,
+        j,
+        token,
+        pattern: Pattern::Date {
+            year,
+            month,
+            day,
+            separator,
+        },
+        guesses,
+    }
+}
+
+fn bruteforce_match(chars: &[char], i: usize, j: usize) -> PatternMatch {
+    let length = j - i + 1;
+    let min_guesses = if length == 1 {
+        MIN_SUBMATCH_GUESSES_SINGLE_CHAR + 1.0
+    } else {
+        MIN_SUBMATCH_GUESSES_MULTI_CHAR + 1.0
+    };
+    PatternMatch {
+        i,
+        j,
+        token: token(chars, i, j),
+        pattern: Pattern::BruteForce,
+        guesses: BRUTEFORCE_CARDINALITY.powi(length as i32).max(min_guesses),
+    }
+}
+
+struct Step {
+    /// Total guesses of the sequence
+    g: f64,
+    /// Product of match guesses in the sequence
+    pi: f64,
+    m: PatternMatch,
+}
+
+/// Find the sequence of non-overlapping matches that needs the fewest guesses
+fn most_guessable(chars: &[char], matches: Vec<PatternMatch>) -> (f64, Vec<PatternMatch>) {
+    let n = chars.len();
+    if n == 0 {
+        return (1.0, Vec::new());
+    }
+
+    let mut by_end: Vec<Vec<PatternMatch>> = vec![Vec::new(); n];
+    for m in matches {
+        by_end[m.j].push(m);
+    }
+
+    // optimal[k][l]: langkah terbaik yang berakhir di karakter k dengan l match
+    let mut optimal: Vec<BTreeMap<usize, Step>> = (0..n).map(|_| BTreeMap::new()).collect();
+
+    for (k, ends) in by_end.iter().enumerate() {
+        for m in ends {
+            if m.i == 0 {
+                update(&mut optimal, m.clone(), 1, n);
+            } else {
+                let lengths: Vec<usize> = optimal[m.i - 1].keys().copied().collect();
+                for l in lengths {
+                    update(&mut optimal, m.clone(), l + 1, n);
+                }
+            }
+        }
+
+        update(&mut optimal, bruteforce_match(chars, 0, k), 1, n);
+        for i in 1..=k {
+            let lengths: Vec<usize> = optimal[i - 1]
+                .iter()
+                .filter(|(_, step)| step.m.pattern != Pattern::BruteForce)
+                .map(|(l, _)| *l)
+                .collect();
+            for l in lengths {
+                update(&mut optimal, bruteforce_match(chars, i, k), l + 1, n);
+            }
+        }
+    }
+
+    let (mut l, guesses) = optimal[n - 1].iter().map(|(l, step)| (*l, step.g)).fold(
+        (0, f64::INFINITY),
+        |best, cur| if cur.1 < best.1 { cur } else { best },
+    );
+
+    let mut sequence = Vec::new();
+    let mut k = n;
+    while k > 0 && l > 0 {
+        let step = &optimal[k - 1][&l];
+        sequence.push(step.m.clone());
+        k = step.m.i;
+        l -= 1;
+    }
+    sequence.reverse();
+
+    (guesses, sequence)
+}
+
+fn update(optimal: &mut [BTreeMap<usize, Step>], m: PatternMatch, l: usize, n: usize) {
+    let k = m.j;
+    let token_length = m.j - m.i + 1;
+
+    let mut guesses = m.guesses;
+    if token_length < n {
+        let min_guesses = if token_length == 1 {
+            MIN_SUBMATCH_GUESSES_SINGLE_CHAR
+        } else {
+            MIN_SUBMATCH_GUESSES_MULTI_CHAR
+        };
+        guesses = guesses.max(min_guesses);
+    }
+
+    let mut pi = guesses;
+    if l > 1 {
+        pi *= optimal[m.i - 1][&(l - 1)].pi;
+    }
+    let g = factorial(l) * pi + MIN_GUESSES_BEFORE_GROWING_SEQUENCE.powi(l as i32 - 1);
+
+    // Abaikan jika ada urutan yang lebih pendek dengan tebakan lebih sedikit
+    if optimal[k]
+        .iter()
+        .any(|(competing_l, step)| *competing_l <= l && step.g <= g)
+    {
+        return;
+    }
+    optimal[k].insert(l, Step { g, pi, m });
+}
+
+fn score(guesses: f64) -> u8 {
+    const DELTA: f64 = 5.0;
+    if guesses < 1e3 + DELTA {
+        0
+    } else if guesses < 1e6 + DELTA {
+        1
+    } else if guesses < 1e8 + DELTA {
+        2
+    } else if guesses < 1e10 + DELTA {
+        3
+    } else {
+        4
+    }
+}
+
+fn feedback(score: u8, sequence: &[PatternMatch]) -> Feedback {
+    if sequence.is_empty() {
+        return Feedback {
+            warning: None,
+            suggestions: vec![Suggestion::UseFewWords, Suggestion::NoNeedForSymbols],
+        };
+    }
+    if score > 2 {
+        return Feedback::default();
+    }
+
+    let longest = sequence
+        .iter()
+        .max_by_key(|m| m.j - m.i)
+        .expect("sequence is not empty");
+    let is_sole_match = sequence.len() == 1;
+
+    let mut suggestions = vec![Suggestion::AddAnotherWord];
+    let warning = match &longest.pattern {
+        Pattern::Dictionary {
+            dictionary,
+            rank,
+            reversed,
+            l33t,
+            ..
+        } => {
+            let warning = match dictionary {
+                Dictionary::Passwords if is_sole_match && !l33t && !reversed => {
+                    if *rank <= 10 {
+                        Some(Warning::TopTenPassword)
+                    } else if *rank <= 100 {
+                        Some(Warning::TopHundredPassword)
+                    } else {
+                        Some(Warning::CommonPassword)
+                    }
+                }
+                Dictionary::Passwords if longest.guesses.log10() <= 4.0 => {
+                    Some(Warning::SimilarToCommonPassword)
+                }
+                Dictionary::English | Dictionary::Indonesian if is_sole_match => {
+                    Some(Warning::WordByItself)
+                }
+                Dictionary::Names if is_sole_match => Some(Warning::NamesByThemselves),
+                Dictionary::Names => Some(Warning::CommonNames),
+                Dictionary::UserInputs => Some(Warning::UserInputs),
+                _ => None,
+            };
+
+            let first_upper = longest
+                .token
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_uppercase());
+            let all_upper = longest.token.to_uppercase() == longest.token
+                && longest.token.to_lowercase() != longest.token;
+            if all_upper {
+                suggestions.push(Suggestion::AllUppercase);
+            } else if first_upper {
+                suggestions.push(Suggestion::Capitalization);
+            }
+            if *reversed && longest.token.chars().count() >= 4 {
+                suggestions.push(Suggestion::ReversedWords);
+            }
+            if *l33t {
+                suggestions.push(Suggestion::L33t);
+            }
+            warning
+        }
+        Pattern::Spatial { turns, .. } => {
+            suggestions.push(Suggestion::LongerKeyboardPattern);
+            if *turns == 1 {
+                Some(Warning::StraightRowsOfKeys)
+            } else {
+                Some(Warning::ShortKeyboardPatterns)
+            }
+        }
+        Pattern::Repeat { base_token, .. } => {
+            suggestions.push(Suggestion::AvoidRepeats);
+            if base_token.chars().count() == 1 {
+                Some(Warning::RepeatedCharacters)
+            } else {
+                Some(Warning::RepeatedPatterns)
+            }
+        }
+        Pattern::Sequence { .. } => {
+            suggestions.push(Suggestion::AvoidSequences);
+            Some(Warning::Sequences)
+        }
+        Pattern::Year { .. } => {
+            suggestions.push(Suggestion::AvoidRecentYears);
+            Some(Warning::RecentYears)
+        }
+        Pattern::Date { .. } => {
+            suggestions.push(Suggestion::AvoidDates);
+            Some(Warning::Dates)
+        }
+        Pattern::BruteForce => None,
+    };
+
+    Feedback {
+        warning,
+        suggestions,
+    }
+}
+
+fn n_ck(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    let k = k.min(n - k);
+    (1..=k).fold(1.0, |acc, i| acc * (n + 1 - i) as f64 / i as f64)
+}
+
+fn factorial(n: usize) -> f64 {
+    (1..=n).fold(1.0, |acc, i| acc * i as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(password: &str) -> Vec<Pattern> {
+        estimate_strength(password, &[])
+            .sequence
+            .into_iter()
+            .map(|m| m.pattern)
+            .collect()
+    }
+
+    #[test]
+    fn test_score() {
+        let cases = vec![
+            ("", 0),
+            ("password", 0),
+            ("qwerty", 0),
+            ("sayangku", 0),
+            ("aaaaaaaa", 0),
+            ("abcdefgh", 0),
+            ("17081945", 1),
+            ("Tr0ub4dor&3xK9#mPq", 4),
+            ("kucing-hitam-makan-durian-di-bulan", 4),
+        ];
+
+        for (input, expected) in cases {
+            let got = estimate_strength(input, &[]).score;
+            assert_eq!(
+                got, expected,
+                "estimate_strength({:?}).score should be {:?}, got {:?}",
+                input, expected, got
+            );
+        }
+    }
+
+    #[test]
+    fn test_patterns() {
+        assert!(matches!(
+            patterns("password")[..],
+            [Pattern::Dictionary {
+                dictionary: Dictionary::Passwords,
+                rank: 2,
+                ..
+            }]
+        ));
+        assert!(matches!(
+            patterns("gnicuk")[..],
+            [Pattern::Dictionary { reversed: true, .. }]
+        ));
+        assert!(matches!(
+            patterns("p4ssw0rd")[..],
+            [Pattern::Dictionary { l33t: true, .. }]
+        ));
+        assert!(matches!(
+            patterns("zxcvfr")[..],
+            [Pattern::Spatial { turns: 2, .. }]
+        ));
+        assert!(matches!(
+            patterns("97531")[..],
+            [Pattern::Sequence { ascending: false }]
+        ));
+        assert!(matches!(
+            patterns("17-08-1945")[..],
+            [Pattern::Date {
+                year: 1945,
+                month: 8,
+                day: 17,
+                separator: Some('-')
+            }]
+        ));
+        assert!(matches!(
+            patterns("kucingkucingkucing")[..],
+            [Pattern::Repeat {
+                repeat_count: 3,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_user_inputs() {
+        let without = estimate_strength("portalnesiaditya", &[]);
+        let with = estimate_strength(
+            "portalnesiaditya",
+            &["Putu Aditya", "aditya@portalnesia.com"],
+        );
+        assert!(with.guesses < without.guesses);
+        assert!(with.sequence.iter().any(|m| matches!(
+            m.pattern,
+            Pattern::Dictionary {
+                dictionary: Dictionary::UserInputs,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_feedback() {
+        let strength = estimate_strength("password", &[]);
+        assert_eq!(strength.feedback.warning, Some(Warning::TopTenPassword));
+        assert_eq!(
+            strength.feedback.warning_message(Language::Indonesian),
+            Some("Ini adalah salah satu dari 10 kata sandi paling umum.")
+        );
+
+        let strength = estimate_strength("Kucing", &[]);
+        assert_eq!(strength.feedback.warning, Some(Warning::WordByItself));
+        assert!(strength
+            .feedback
+            .suggestions
+            .contains(&Suggestion::Capitalization));
+
+        let strength = estimate_strength("Tr0ub4dor&3xK9#mPq", &[]);
+        assert_eq!(strength.feedback, Feedback::default());
+    }
+
+    #[test]
+    fn test_display_time() {
+        let cases = vec![
+            (0.5, Language::English, "less than a second"),
+            (1.0, Language::English, "1 second"),
+            (90.0, Language::English, "2 minutes"),
+            (90.0, Language::Indonesian, "2 menit"),
+            (86400.0 * 3.0, Language::Indonesian, "3 hari"),
+            (1e12, Language::English, "centuries"),
+            (1e12, Language::Indonesian, "berabad-abad"),
+        ];
+
+        for (seconds, lang, expected) in cases {
+            let got = display_time(seconds, lang);
+            assert_eq!(
+                got, expected,
+                "display_time({:?}, {:?}) should be {:?}, got {:?}",
+                seconds, lang, expected, got
+            );
+        }
+    }
+}
@@ -0,0 +1,224 @@
+/*
+ * Copyright (c) Portalnesia - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Putu Aditya <aditya@portalnesia.com>
+ */
+
+use crate::constant_time_eq;
+use crate::helper::{nanoid_format, ALPHANUMERIC_CHARS, SAFE_CHARS};
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Length of the public selector
+pub const SELECTOR_LENGTH: usize = 16;
+/// Length of the secret verifier, about 192 bits of entropy
+pub const VERIFIER_LENGTH: usize = 32;
+/// Separator between selector and verifier
+const SEPARATOR: char = '.';
+
+/// Error returned when verifying a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenError {
+    /// The token is not `<selector>.<verifier>`
+    Malformed,
+    /// The verifier does not match the stored hash
+    Invalid,
+    /// The token has expired
+    Expired,
+}
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenError::Malformed => write!(f, "malformed token"),
+            TokenError::Invalid => write!(f, "invalid token"),
+            TokenError::Expired => write!(f, "token has expired"),
+        }
+    }
+}
+
+impl Error for TokenError {}
+
+/// Newly issued token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssuedToken {
+    /// URL-safe token to send to the user, `<selector>.<verifier>`
+    pub token: String,
+    /// Public selector to store and look up
+    pub selector: String,
+    /// Hex SHA-256 hash of the verifier to store
+    pub verifier_hash: String,
+    /// Expiry time to store, Unix timestamp in seconds
+    pub expires_at: u64,
+}
+
+/// Single-use token for password reset and email verification links
+///
+/// A token is split into a public selector, used to find the row in the database, and a secret
+/// verifier. Only the SHA-256 hash of the verifier is stored, so a database leak does not reveal
+/// usable tokens, and the lookup by selector does not leak timing information about the secret.
+///
+/// ## Example
+///
+/// ```
+/// use pn_utils::token::SplitToken;
+/// use std::time::Duration;
+///
+/// // Issue: store selector, verifier_hash and expires_at, send token to the user
+/// let issued = SplitToken::issue(Duration::from_secs(3600));
+/// let link = format!("https://portalnesia.com/reset?token={}", issued.token);
+///
+/// // Verify: find the row by selector, then check the verifier
+/// let token = SplitToken::parse(&issued.token).expect("malformed token");
+/// assert_eq!(token.selector(), issued.selector);
+/// token
+///     .verify(&issued.verifier_hash, issued.expires_at)
+///     .expect("invalid token");
+/// // Delete the row so the token can only be used once
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitToken {
+    selector: String,
+    verifier: String,
+}
+
+impl SplitToken {
+    /// Issue a new token that expires after `ttl`
+    pub fn issue(ttl: Duration) -> IssuedToken {
+        let selector = nanoid_format(&ALPHANUMERIC_CHARS, SELECTOR_LENGTH);
+        let verifier = nanoid_format(&SAFE_CHARS, VERIFIER_LENGTH);
+
+        IssuedToken {
+            token: format!("{}{}{}", selector, SEPARATOR, verifier),
+            verifier_hash: hash_verifier(&verifier),
+            expires_at: unix_now().saturating_add(ttl.as_secs()),
+            selector,
+        }
+    }
+
+    /// Split the token received from the user
+    pub fn parse(token: &str) -> Result<Self, TokenError> {
+        let (selector, verifier) = token.split_once(SEPARATOR).ok_or(TokenError::Malformed)?;
+
+        if selector.len() != SELECTOR_LENGTH
+            || verifier.len() != VERIFIER_LENGTH
+            || !selector.chars().all(|c| ALPHANUMERIC_CHARS.contains(&c))
+            || !verifier.chars().all(|c| SAFE_CHARS.contains(&c))
+        {
+            return Err(TokenError::Malformed);
+        }
+
+        Ok(SplitToken {
+            selector: selector.to_string(),
+            verifier: verifier.to_string(),
+        })
+    }
+
+    /// Public selector, used to find the stored hash
+    pub fn selector(&self) -> &str {
+        &self.selector
+    }
+
+    /// Compare the verifier with the stored hash in constant time, and check the expiry
+    ///
+    /// `expires_at` is the Unix timestamp in seconds stored from [`IssuedToken::expires_at`].
+    pub fn verify(&self, verifier_hash: &str, expires_at: u64) -> Result<(), TokenError> {
+        self.verify_at(verifier_hash, expires_at, unix_now())
+    }
+
+    fn verify_at(&self, verifier_hash: &str, expires_at: u64, now: u64) -> Result<(), TokenError> {
+        let computed = hash_verifier(&self.verifier);
+        // Bandingkan hash dulu, supaya token yang salah tidak bisa membedakan kadaluarsa
+        if !constant_time_eq(
+            computed.as_bytes(),
+            verifier_hash.to_ascii_lowercase().as_bytes(),
+        ) {
+            return Err(TokenError::Invalid);
+        }
+        if now >= expires_at {
+            return Err(TokenError::Expired);
+        }
+        Ok(())
+    }
+}
+
+fn hash_verifier(verifier: &str) -> String {
+    hex::encode(Sha256::digest(verifier.as_bytes()))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_and_verify() {
+        let issued = SplitToken::issue(Duration::from_secs(60));
+        assert_eq!(issued.token.len(), SELECTOR_LENGTH + 1 + VERIFIER_LENGTH);
+        assert!(issued.expires_at > unix_now());
+
+        let token = SplitToken::parse(&issued.token).expect("failed to parse token");
+        assert_eq!(token.selector(), issued.selector);
+        assert_eq!(
+            token.verify(&issued.verifier_hash, issued.expires_at),
+            Ok(())
+        );
+        assert_eq!(
+            token.verify(&issued.verifier_hash.to_uppercase(), issued.expires_at),
+            Ok(())
+        );
+
+        // Token lain dengan selector yang sama
+        let other = SplitToken::issue(Duration::from_secs(60));
+        assert_eq!(
+            token.verify(&other.verifier_hash, issued.expires_at),
+            Err(TokenError::Invalid)
+        );
+
+        let now = unix_now();
+        assert_eq!(
+            token.verify_at(&issued.verifier_hash, now, now),
+            Err(TokenError::Expired)
+        );
+        assert_eq!(
+            token.verify_at(&other.verifier_hash, now, now),
+            Err(TokenError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        let verifier = "a".repeat(VERIFIER_LENGTH);
+        let cases = vec![
+            String::new(),
+            "abc".to_string(),
+            format!("{}{}", "a".repeat(SELECTOR_LENGTH), verifier),
+            format!("{}.{}", "a".repeat(SELECTOR_LENGTH - 1), verifier),
+            format!("{}.{}a", "a".repeat(SELECTOR_LENGTH), verifier),
+            format!("{}-.{}", "a".repeat(SELECTOR_LENGTH - 1), verifier),
+            format!(
+                "{}.{}",
+                "a".repeat(SELECTOR_LENGTH),
+                "é".repeat(VERIFIER_LENGTH / 2)
+            ),
+        ];
+        for input in cases {
+            let got = SplitToken::parse(&input);
+            assert_eq!(
+                got,
+                Err(TokenError::Malformed),
+                "SplitToken::parse({:?}) should be malformed, got {:?}",
+                input,
+                got
+            );
+        }
+    }
+}